@@ -0,0 +1,80 @@
+//! Compares the day 14 tilt implementations on generated platforms of increasing size
+//!
+//! Run with `cargo bench --bench day14_tilt`, both find the load after a billion spin cycles.
+//! Square platforms are followed by wide, short ones made from the same cells.
+
+use advent_2023::{bench::Spread, days::day14_rocks_roll, generate};
+use std::time::{Duration, Instant};
+
+const SIZES: [usize; 4] = [25, 50, 100, 200];
+/// Rows of the wide platforms, each reshaped from the last square
+const WIDE_HEIGHTS: [usize; 2] = [3, 12];
+const SEED: u64 = 14;
+const ITERATIONS: usize = 3;
+const SPIN_CYCLES: usize = 1_000_000_000;
+
+fn main() -> anyhow::Result<()> {
+    println!(
+        "{:>9} {:>12} {:>12} {:>8}",
+        "size", "grid", "packed", "speedup"
+    );
+    let rules = day14_rocks_roll::Rules::default();
+    let mut shapes = vec![];
+    for size in SIZES {
+        shapes.push((
+            format!("{size}x{size}"),
+            generate::generate(14, size, SEED)?,
+        ));
+    }
+    let largest = generate::generate(14, SIZES[SIZES.len() - 1], SEED)?;
+    for height in WIDE_HEIGHTS {
+        let input = reshape(&largest, height);
+        let width = input.lines().next().map_or(0, str::len);
+        shapes.push((format!("{height}x{width}"), input));
+    }
+    for (size, input) in shapes {
+        let platforms = day14_rocks_roll::parse_grids(&input)?;
+
+        let (grid_load, grid) =
+            measure(|| day14_rocks_roll::load_after_cycles(&platforms, &rules, SPIN_CYCLES));
+        let (packed_load, packed) =
+            measure(|| day14_rocks_roll::load_after_cycles_packed(&platforms, &rules, SPIN_CYCLES));
+        anyhow::ensure!(
+            grid_load == packed_load,
+            "size {size}: grid load {grid_load} != packed load {packed_load}"
+        );
+
+        let speedup = grid.median.as_secs_f64() / packed.median.as_secs_f64();
+        println!(
+            "{size:>9} {:>12} {:>12} {speedup:>7.1}x",
+            format!("{:.2?}", grid.median),
+            format!("{:.2?}", packed.median),
+        );
+    }
+    Ok(())
+}
+
+/// Lays the cells of the platform out again in `height` rows, dropping any left over
+fn reshape(input: &str, height: usize) -> String {
+    let cells: Vec<char> = input.lines().flat_map(str::chars).collect();
+    let width = cells.len() / height;
+    cells
+        .chunks_exact(width)
+        .take(height)
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the result and the spread of durations over the iterations
+fn measure(f: impl Fn() -> usize) -> (usize, Spread) {
+    let mut result = 0;
+    let samples: Vec<Duration> = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            result = std::hint::black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    (result, Spread::new(samples).expect("nonzero iterations"))
+}
@@ -0,0 +1,202 @@
+//! Repeated runs of each [`Solver`](crate::Solver) phase, for tracking performance
+
+use crate::{
+    runner::{Entry, Timings},
+    PartSelection, RuleArgs,
+};
+use std::{
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Spread of the durations for one phase, across all iterations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spread {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+impl Spread {
+    /// Returns `None` if there are no samples
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let min = *samples.first()?;
+        let max = *samples.last()?;
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+        Some(Self { min, median, max })
+    }
+}
+
+/// Spread of each phase for one day (parts are `None` if not selected)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub day: u8,
+    pub name: &'static str,
+    /// Input file, when benchmarking multiple inputs for the day
+    pub input: Option<PathBuf>,
+    pub iterations: NonZeroU32,
+    pub parse: Spread,
+    pub part1: Option<Spread>,
+    pub part2: Option<Spread>,
+}
+impl Summary {
+    /// Runs the selected phases of the day for the specified number of iterations
+    pub fn measure(
+        entry: &Entry,
+        filename: Option<&Path>,
+        input: &str,
+        (parts, rules): (PartSelection, &RuleArgs),
+        iterations: NonZeroU32,
+    ) -> anyhow::Result<Self> {
+        let mut parse = vec![];
+        let mut part1 = vec![];
+        let mut part2 = vec![];
+        for _ in 0..iterations.get() {
+            let Timings {
+                parse: parse_time,
+                part1: part1_time,
+                part2: part2_time,
+            } = entry.run_with_rules(input, parts, rules)?.timings;
+            parse.push(parse_time);
+            part1.extend(part1_time);
+            part2.extend(part2_time);
+        }
+        Ok(Self {
+            day: entry.day,
+            name: entry.name,
+            input: filename.map(Path::to_path_buf),
+            iterations,
+            parse: Spread::new(parse).expect("nonzero iterations"),
+            part1: Spread::new(part1),
+            part2: Spread::new(part2),
+        })
+    }
+    /// Returns the label and spread for each measured phase, in order
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, Spread)> {
+        let parts = [("part1", self.part1), ("part2", self.part2)]
+            .into_iter()
+            .filter_map(|(label, spread)| Some((label, spread?)));
+        std::iter::once(("parse", self.parse)).chain(parts)
+    }
+}
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            day,
+            name,
+            ref input,
+            iterations,
+            ..
+        } = *self;
+        write!(f, "Day {day} ({name})")?;
+        if let Some(input) = input {
+            write!(f, " {input:?}")?;
+        }
+        write!(f, ", {iterations} iterations")?;
+        for (label, Spread { min, median, max }) in self.phases() {
+            write!(
+                f,
+                "\n  {label:5}  min {min:>12.3?}  median {median:>12.3?}  max {max:>12.3?}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes one CSV row per day and phase, with the durations in milliseconds
+pub fn write_csv(mut writer: impl std::io::Write, summaries: &[Summary]) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "day,name,input,phase,iterations,min_ms,median_ms,max_ms"
+    )?;
+    for summary in summaries {
+        let Summary {
+            day,
+            name,
+            ref input,
+            iterations,
+            ..
+        } = *summary;
+        let input = input.as_deref().map_or(String::new(), csv_field);
+        for (phase, Spread { min, median, max }) in summary.phases() {
+            let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+            writeln!(
+                writer,
+                "{day},{name},{input},{phase},{iterations},{:.6},{:.6},{:.6}",
+                ms(min),
+                ms(median),
+                ms(max)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes the path if needed, per RFC 4180
+fn csv_field(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_csv, Spread, Summary};
+    use std::{num::NonZeroU32, path::PathBuf, time::Duration};
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn spread() {
+        assert_eq!(Spread::new(vec![]), None);
+        let odd = Spread::new(millis(&[5, 1, 3])).unwrap();
+        assert_eq!(odd.min, Duration::from_millis(1));
+        assert_eq!(odd.median, Duration::from_millis(3));
+        assert_eq!(odd.max, Duration::from_millis(5));
+
+        let even = Spread::new(millis(&[8, 2, 4, 1])).unwrap();
+        assert_eq!(even.min, Duration::from_millis(1));
+        assert_eq!(even.median, Duration::from_millis(3));
+        assert_eq!(even.max, Duration::from_millis(8));
+    }
+
+    #[test]
+    fn csv() {
+        let spread = Spread::new(millis(&[1, 2, 3])).unwrap();
+        let summary = Summary {
+            day: 14,
+            name: "rocks_roll",
+            input: None,
+            iterations: NonZeroU32::new(3).unwrap(),
+            parse: spread,
+            part1: Some(spread),
+            part2: None,
+        };
+        let with_input = Summary {
+            input: Some(PathBuf::from("a,b.txt")),
+            part1: None,
+            ..summary.clone()
+        };
+        let mut output = vec![];
+        write_csv(&mut output, &[summary, with_input]).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "day,name,input,phase,iterations,min_ms,median_ms,max_ms
+14,rocks_roll,,parse,3,1.000000,2.000000,3.000000
+14,rocks_roll,,part1,3,1.000000,2.000000,3.000000
+14,rocks_roll,\"a,b.txt\",parse,3,1.000000,2.000000,3.000000
+"
+        );
+    }
+}
@@ -0,0 +1,6 @@
+use clap::Parser;
+
+fn main() -> anyhow::Result<()> {
+    let args = advent_2023::Args::parse();
+    advent_2023::runner::main(args)
+}
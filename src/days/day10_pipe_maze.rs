@@ -1,101 +1,57 @@
-use advent_2023::{
+use crate::{
     direction::{Direction, DirectionH, DirectionV, EAST, NORTH, SOUTH, WEST},
+    grid::Grid,
+    log_debug, log_trace,
     point::Point,
+    search, ParseError,
 };
 
-fn main() -> anyhow::Result<()> {
-    println!("hello, pipe maze");
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 10;
+    type Parsed = Maze;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let input = advent_2023::get_input_string()?;
-
-    let grid = Grid::try_new(&input)?;
-
-    let (grid_usage, longest) = find_longest_step(grid)?;
-    println!("Longest step: {longest}");
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Maze::try_new(input)
+    }
+    fn part1(maze: &Maze) -> anyhow::Result<usize> {
+        let (_, longest) = find_longest_step(maze.clone())?;
+        Ok(longest)
+    }
+    fn part2(maze: &Maze) -> anyhow::Result<usize> {
+        let (grid_usage, _) = find_longest_step(maze.clone())?;
+        find_area_enclosed(&grid_usage)
+    }
+}
 
-    let area = find_area_enclosed(&grid_usage)?;
-    println!("Area enclosed: {area}");
+fn find_longest_step(maze: Maze) -> anyhow::Result<(GridUsage, usize)> {
+    log_debug!("Grid:\n{}", maze.grid);
 
-    Ok(())
-}
+    let (start, directions) = maze.get_start_directions();
+    let explored = search::bfs(start, |&point| maze.get_linked(point, &directions));
+    let Some(longest) = explored.distances().map(|(_, distance)| distance).max() else {
+        anyhow::bail!("no loop found from start {start:?}")
+    };
 
-fn find_longest_step(grid: Grid) -> anyhow::Result<(GridUsage, usize)> {
-    println!("Grid:\n{grid}");
-
-    let (start, directions) = grid.get_start_directions();
-    let mut state: Vec<(Point, Direction)> = directions
-        .iter()
-        .copied()
-        .map(|direction| (start, direction))
-        .collect();
-
-    let mut used = vec![None; grid.cells.len()];
-    {
-        // mark Start as used
-        let index = start
-            .index_for_width(grid.width)
-            .expect("start position valid");
-        let used = used.get_mut(index).expect("start position valid");
+    let mut used = maze.grid.empty_with(|| None);
+    for &point in explored.nodes() {
+        let used = used.get_mut(point).expect("explored point is valid");
         *used = Some(Used);
     }
-
-    for step in 1.. {
-        // advance state
-        for (point, direction) in state.iter_mut() {
-            let Some((new_point, new_direction)) = grid.get_next_linked(*point, *direction) else {
-                anyhow::bail!("no next found for {point:?} {direction:?}")
-            };
-            *point = new_point;
-            *direction = new_direction;
-        }
-        // mark cells used
-        for (point, _) in &state {
-            let index = point
-                .index_for_width(grid.width)
-                .expect("previous state point is still valid");
-            let used = used
-                .get_mut(index)
-                .expect("previous state point indexes used");
-            let _prev_used = std::mem::replace(used, Some(Used));
-        }
-        // detect collisions
-        let ended: Vec<bool> = state
-            .iter()
-            .enumerate()
-            .map(|(index, (point, _))| {
-                // check if two different points are equal
-                state
-                    .iter()
-                    .enumerate()
-                    .any(|(i, (p, _))| index != i && point == p)
-            })
-            .collect();
-        for (ended_index, _) in ended
-            .into_iter()
-            .enumerate()
-            .filter(|&(_, ended)| ended)
-            .rev()
-        {
-            state.remove(ended_index);
-        }
-        if state.is_empty() {
-            // end
-            let longest = step;
-            let usage = GridUsage::new(grid, used, (start, directions))?;
-            return Ok((usage, longest));
-        }
-    }
-    anyhow::bail!("overflowed step count tracing the paths")
+    let usage = GridUsage::new(maze.grid, used, (start, directions))?;
+    Ok((usage, longest))
 }
 
 fn find_area_enclosed(grid_usage: &GridUsage) -> anyhow::Result<usize> {
-    println!("Grid usage:\n{grid_usage}");
+    log_debug!("Grid usage:\n{grid_usage}");
 
     let mut area = 0;
     'outer: for row in 0.. {
         let mut inside_boundary = false;
         let mut vertical_bend_prev = None;
-        for col in 0..grid_usage.grid.width {
+        for col in 0..grid_usage.grid.width() {
             let point = Point { row, col };
             let Some((cell, used)) = grid_usage.get_cell_used(point) else {
                 break 'outer;
@@ -137,10 +93,10 @@ fn find_area_enclosed(grid_usage: &GridUsage) -> anyhow::Result<usize> {
                 if boundary_change {
                     inside_boundary = !inside_boundary;
                     let inside = if inside_boundary { "IN" } else { "OUT" };
-                    println!("{inside} at Point({row}, {col}) cell {cell:?}");
+                    log_trace!("{inside} at Point({row}, {col}) cell {cell:?}");
                 }
             } else if inside_boundary {
-                println!("\tInside boundary at Point({row}, {col}) cell {cell:?}");
+                log_trace!("\tInside boundary at Point({row}, {col}) cell {cell:?}");
                 area += 1;
             }
         }
@@ -150,20 +106,20 @@ fn find_area_enclosed(grid_usage: &GridUsage) -> anyhow::Result<usize> {
 
 #[derive(Debug)]
 struct GridUsage {
-    grid: Grid,
-    used: Vec<Option<Used>>,
+    grid: Grid<Cell>,
+    used: Grid<Option<Used>>,
 }
 impl GridUsage {
     fn new(
-        mut grid: Grid,
-        used: Vec<Option<Used>>,
+        mut grid: Grid<Cell>,
+        used: Grid<Option<Used>>,
         (start, directions): (Point, Vec<Direction>),
     ) -> anyhow::Result<Self> {
-        assert_eq!(grid.cells.len(), used.len());
+        assert_eq!(grid.cells().len(), used.cells().len());
 
         // modify START into appropriate 2-direction pipe
         {
-            let Some(start_cell) = grid.get_cell_mut(start) else {
+            let Some(start_cell) = grid.get_mut(start) else {
                 anyhow::bail!("start cell not found at {start:?}")
             };
             let [direction1, direction2]: [Direction; 2] = match directions.try_into() {
@@ -185,77 +141,30 @@ impl GridUsage {
         Ok(Self { grid, used })
     }
     fn get_cell_used(&self, point: Point) -> Option<(Cell, Option<Used>)> {
-        let index = point.index_for_width(self.grid.width)?;
-        if let Some(cell) = self.grid.cells.get(index).copied() {
-            let used = self
-                .used
-                .get(index)
-                .copied()
-                .expect("index with cell is in bounds for used");
-            Some((cell, used))
-        } else {
-            None
-        }
+        let cell = self.grid.get(point).copied()?;
+        let used = self
+            .used
+            .get(point)
+            .copied()
+            .expect("point with cell is in bounds for used");
+        Some((cell, used))
     }
 }
 
-#[derive(Debug)]
-struct Grid {
-    width: usize,
-    cells: Vec<Cell>,
+#[derive(Clone, Debug)]
+pub struct Maze {
+    grid: Grid<Cell>,
     start: Point,
 }
-impl Grid {
+impl Maze {
     fn try_new(input: &str) -> anyhow::Result<Self> {
-        let mut cells = vec![];
-        let mut width: Option<usize> = None;
-        let mut start = None;
-        for (row, line) in input.lines().enumerate() {
-            let row_cells = line
-                .chars()
-                .map(Cell::try_from)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|c| anyhow::anyhow!("unrecognized char {c}"))?;
-            let new_width = row_cells.len();
-            match &mut width {
-                &mut Some(width) if width == new_width => {}
-                Some(old_width) => {
-                    anyhow::bail!("width mismatch {old_width} <---> {new_width}")
-                }
-                width @ None => {
-                    *width = Some(new_width);
-                }
-            }
-            if start.is_none() {
-                // search for `Start` in current row
-                start = row_cells
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .find_map(|(col, cell)| (cell == Cell::Start).then_some(Point { row, col }))
-            }
-            cells.extend(row_cells);
-        }
-        let Some(width) = width else {
+        let Some(grid) = Grid::parse(input, Cell::try_from)? else {
             anyhow::bail!("empty input")
         };
-        let Some(start) = start else {
+        let Some((start, _)) = grid.iter().find(|&(_, &cell)| cell == Cell::Start) else {
             anyhow::bail!("no start location given")
         };
-        Ok(Self {
-            width,
-            cells,
-            start,
-        })
-    }
-
-    fn get_cell(&self, point: Point) -> Option<Cell> {
-        let index = point.index_for_width(self.width)?;
-        self.cells.get(index).copied()
-    }
-    fn get_cell_mut(&mut self, point: Point) -> Option<&mut Cell> {
-        let index = point.index_for_width(self.width)?;
-        self.cells.get_mut(index)
+        Ok(Self { grid, start })
     }
 
     /// Returns the start location, and valid outward travel directions
@@ -266,8 +175,7 @@ impl Grid {
             .copied()
             .filter_map(|direction| {
                 // START --direction-> TARGET
-                let target = direction.of(start)?;
-                let target_cell = self.get_cell(target)?;
+                let (_, target_cell) = self.grid.neighbor(start, direction)?;
                 // if TARGET connects any of reverse direction
                 target_cell
                     .connects_any(-direction)
@@ -277,14 +185,25 @@ impl Grid {
             .collect();
         (start, directions)
     }
-    /// Returns the next location/direction given the previous location/direction
-    fn get_next_linked(&self, prev: Point, direction: Direction) -> Option<(Point, Direction)> {
-        let current = direction.of(prev)?;
-        let current_cell = self.get_cell(current)?;
-
-        let reverse_entry = -direction;
-        let next_direction = current_cell.connects_any(reverse_entry)?;
-        Some((current, next_direction))
+    /// Returns the points linked to `point` along the pipes, using `start_directions` for START
+    fn get_linked<'a>(
+        &'a self,
+        point: Point,
+        start_directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        let directions = if point == self.start {
+            start_directions.to_vec()
+        } else {
+            self.grid[point]
+                .get_directions()
+                .map(|(direction1, direction2)| vec![direction1, direction2])
+                .unwrap_or_default()
+        };
+        directions.into_iter().filter_map(move |direction| {
+            let (next, next_cell) = self.grid.neighbor(point, direction)?;
+            let links_back = next == self.start || next_cell.connects_any(-direction).is_some();
+            links_back.then_some(next)
+        })
     }
 }
 
@@ -315,9 +234,7 @@ impl Cell {
         }
     }
     fn connects_any(&self, from: Direction) -> Option<Direction> {
-        let Some((connection1, connection2)) = self.get_directions() else {
-            return None;
-        };
+        let (connection1, connection2) = self.get_directions()?;
         if from == connection1 {
             Some(connection2)
         } else if from == connection2 {
@@ -352,7 +269,7 @@ impl From<(DirectionV, DirectionH)> for Cell {
     }
 }
 impl TryFrom<char> for Cell {
-    type Error = char;
+    type Error = ParseError;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         let cell = match value {
             '|' => Self::PipeVertical,
@@ -364,7 +281,7 @@ impl TryFrom<char> for Cell {
             '.' => Self::Empty,
             'S' => Self::Start,
             other => {
-                return Err(other);
+                return Err(ParseError::unexpected_char(other));
             }
         };
         Ok(cell)
@@ -386,62 +303,19 @@ impl std::fmt::Display for Cell {
     }
 }
 
-fn fmt_grid<T>(
-    f: &mut std::fmt::Formatter<'_>,
-    width: usize,
-    get_cell: impl Fn(Point) -> Option<T>,
-    fmt_cell: impl Fn(&mut std::fmt::Formatter<'_>, T) -> std::fmt::Result,
-) -> std::fmt::Result {
-    // column header
-    write!(f, "  ")?;
-    for col in 0..width {
-        write!(f, "{}", col % 10)?;
-    }
-    writeln!(f)?;
-
-    for row in 0.. {
-        for col in 0..width {
-            let point = Point { row, col };
-            let Some(cell) = get_cell(point) else {
-                return Ok(());
-            };
-            if col == 0 {
-                // row header
-                write!(f, "{} ", row % 10)?;
-            }
-            fmt_cell(f, cell)?;
-        }
-        writeln!(f)?;
-    }
-    unreachable!()
-}
-
-impl std::fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let width = self.width;
-        let get_cell = |point| self.get_cell(point);
-        let fmt_cell = |f: &mut std::fmt::Formatter<'_>, cell| write!(f, "{cell}");
-        fmt_grid(f, width, get_cell, fmt_cell)
-    }
-}
 impl std::fmt::Display for GridUsage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let width = self.grid.width;
-        let get_cell = |point| self.get_cell_used(point);
-        let fmt_cell = |f: &mut std::fmt::Formatter, (cell, used)| {
-            if let Some(Used) = used {
-                write!(f, "{cell}")
-            } else {
-                write!(f, " ")
-            }
-        };
-        fmt_grid(f, width, get_cell, fmt_cell)
+        let used_cells = self.grid.map(|point, cell| match self.used.get(point) {
+            Some(Some(Used)) => cell.to_string(),
+            _ => " ".to_string(),
+        });
+        write!(f, "{used_cells}")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{find_area_enclosed, find_longest_step, Grid};
+    use crate::days::day10_pipe_maze::{find_area_enclosed, find_longest_step, Maze};
 
     #[test]
     fn sample_input() {
@@ -451,10 +325,10 @@ mod tests {
 .L-J.
 .....";
 
-        let grid = Grid::try_new(input).unwrap();
-        dbg!(&grid);
+        let maze = Maze::try_new(input).unwrap();
+        dbg!(&maze);
 
-        let (_, longest) = find_longest_step(grid).unwrap();
+        let (_, longest) = find_longest_step(maze).unwrap();
         assert_eq!(longest, 4);
     }
 
@@ -466,10 +340,10 @@ SJ.L7
 |F--J
 LJ...";
 
-        let grid = Grid::try_new(input).unwrap();
-        dbg!(&grid);
+        let maze = Maze::try_new(input).unwrap();
+        dbg!(&maze);
 
-        let (_, longest) = find_longest_step(grid).unwrap();
+        let (_, longest) = find_longest_step(maze).unwrap();
         assert_eq!(longest, 8);
     }
 
@@ -484,9 +358,9 @@ LJ...";
 .|..|.|..|.
 .L--J.L--J.
 ...........";
-        let grid = Grid::try_new(input).unwrap();
+        let maze = Maze::try_new(input).unwrap();
 
-        let (grid_usage, _longest) = find_longest_step(grid).unwrap();
+        let (grid_usage, _longest) = find_longest_step(maze).unwrap();
         let area = find_area_enclosed(&grid_usage).unwrap();
         assert_eq!(area, 4);
     }
@@ -501,9 +375,9 @@ LJ...";
 .|..||..|.
 .L--JL--J.
 ..........";
-        let grid = Grid::try_new(input).unwrap();
+        let maze = Maze::try_new(input).unwrap();
 
-        let (grid_usage, _longest) = find_longest_step(grid).unwrap();
+        let (grid_usage, _longest) = find_longest_step(maze).unwrap();
         let area = find_area_enclosed(&grid_usage).unwrap();
         assert_eq!(area, 4);
     }
@@ -520,9 +394,9 @@ L--J.L7...LJS7F-7L7.
 .....|FJLJ|FJ|F7|.LJ
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
-        let grid = Grid::try_new(input).unwrap();
+        let maze = Maze::try_new(input).unwrap();
 
-        let (grid_usage, _longest) = find_longest_step(grid).unwrap();
+        let (grid_usage, _longest) = find_longest_step(maze).unwrap();
         let area = find_area_enclosed(&grid_usage).unwrap();
         assert_eq!(area, 8);
     }
@@ -539,9 +413,9 @@ L---JF-JLJ.||-FJLJJ7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
-        let grid = Grid::try_new(input).unwrap();
+        let maze = Maze::try_new(input).unwrap();
 
-        let (grid_usage, _longest) = find_longest_step(grid).unwrap();
+        let (grid_usage, _longest) = find_longest_step(maze).unwrap();
         let area = find_area_enclosed(&grid_usage).unwrap();
         assert_eq!(area, 10);
     }
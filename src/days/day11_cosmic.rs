@@ -3,34 +3,42 @@ use std::{
     num::NonZeroUsize,
 };
 
-use advent_2023::{dimension::Dimension, point::Point};
+use crate::{dimension::Dimension, log_debug, log_trace, point::Point, ParseError};
 
+const FACTOR_TWO: NonZeroUsize = const_factor(2);
 const FACTOR_MILLION: NonZeroUsize = const_factor(1_000_000);
 
-fn main() -> anyhow::Result<()> {
-    println!("hello, cosmic...?");
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 11;
+    type Parsed = Galaxies;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let input = advent_2023::get_input_string()?;
-
-    let original = Galaxies::new(&input)?;
-    let expanded = original.expand(FACTOR_MILLION);
-
-    let distances_sum = expanded.get_distances_sum();
-    println!("Sum of distances in expanded: {distances_sum}");
-
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(Galaxies::new(input)?)
+    }
+    fn part1(original: &Galaxies) -> anyhow::Result<usize> {
+        let expanded = original.clone().expand(FACTOR_TWO);
+        Ok(expanded.get_distances_sum())
+    }
+    fn part2(original: &Galaxies) -> anyhow::Result<usize> {
+        let expanded = original.clone().expand(FACTOR_MILLION);
+        Ok(expanded.get_distances_sum())
+    }
 }
 
-struct Galaxies {
+#[derive(Clone)]
+pub struct Galaxies {
     points: BTreeSet<Point>,
     // ONLY for formatting
     max_col: usize,
 }
 impl Galaxies {
-    fn new(input: &str) -> anyhow::Result<Self> {
+    fn new(input: &str) -> Result<Self, ParseError> {
         let mut points = BTreeSet::new();
         for (row, line) in input.lines().enumerate() {
-            for (col, value) in line.chars().enumerate() {
+            for (col, (index, value)) in line.char_indices().enumerate() {
                 match value {
                     '.' => {}
                     '#' => {
@@ -38,19 +46,20 @@ impl Galaxies {
                         points.insert(point);
                     }
                     extra => {
-                        anyhow::bail!("unrecognized symbol {extra:?} at row {row}, col {col}")
+                        let text = &line[index..(index + extra.len_utf8())];
+                        return Err(ParseError::unexpected_char(extra).at(row, line, text));
                     }
                 }
             }
         }
         if points.is_empty() {
-            anyhow::bail!("no galaxies in input")
+            return Err(ParseError::new("no galaxies in input"));
         }
 
         let max_col = points.iter().map(|p| p.col).max().expect("nonempty");
 
         let this = Self { points, max_col };
-        println!("Original galaxies:\n{this}");
+        log_debug!("Original galaxies:\n{this}");
         Ok(this)
     }
     fn max_row(&self) -> usize {
@@ -66,11 +75,11 @@ impl Galaxies {
         for &dimension in Dimension::ALL {
             this = this.expand_dimension(dimension, additive_factor);
         }
-        println!("Expanded galaxies:\n{this}");
+        log_debug!("Expanded galaxies:\n{this}");
         this
     }
     fn expand_dimension(self, dimension: Dimension, additive_factor: usize) -> Self {
-        println!("------ EXPAND DIMENSION: {dimension:?} ------");
+        log_trace!("------ EXPAND DIMENSION: {dimension:?} ------");
         let Self {
             mut points,
             max_col,
@@ -83,11 +92,11 @@ impl Galaxies {
         }
 
         let amount_to_add: BTreeMap<usize, usize> = {
-            println!("Keys: {:?}", points_by_dim.keys().collect::<Vec<_>>());
+            log_trace!("Keys: {:?}", points_by_dim.keys().collect::<Vec<_>>());
             let distances = std::iter::once(None)
                 .chain(points_by_dim.keys().copied().map(Some))
                 .collect::<Vec<_>>();
-            println!("Distances: {distances:?}");
+            log_trace!("Distances: {distances:?}");
             let diffs = distances
                 .windows(2)
                 .map(|values| {
@@ -102,18 +111,18 @@ impl Galaxies {
                     gap_count * additive_factor
                 })
                 .collect::<Vec<_>>();
-            println!("Diffs: {diffs:?}");
+            log_trace!("Diffs: {diffs:?}");
             let cumulative = (0..diffs.len())
                 .map(|index| {
                     // sum all items *before and including* current
                     diffs.iter().take(index + 1).sum()
                 })
                 .collect::<Vec<_>>();
-            println!("cumulative: {cumulative:?}");
+            log_trace!("cumulative: {cumulative:?}");
             let cumulative_map = points_by_dim.keys().copied().zip(cumulative).collect();
             cumulative_map
         };
-        println!("{dimension:?}: amounts to add {amount_to_add:#?}");
+        log_trace!("{dimension:?}: amounts to add {amount_to_add:#?}");
 
         let max_col = if dimension == Dimension::Col {
             let (_, &last_added) = amount_to_add.last_key_value().expect("nonempty");
@@ -150,46 +159,17 @@ impl Galaxies {
                     .iter()
                     .copied()
                     .skip(index_outer + 1)
-                    .map(|point_b| point_a.get_distance(point_b))
+                    .map(|point_b| point_a.manhattan_distance(point_b))
                     .sum::<usize>()
             })
             .sum()
     }
 }
 
-trait TaxicabDistance {
-    fn get_distance(self, other: Self) -> usize;
-}
-impl TaxicabDistance for Point {
-    /// <https://en.wikipedia.org/wiki/Taxicab_geometry>
-    fn get_distance(self, other: Self) -> usize {
-        let Self { row, col } = self;
-        let Self {
-            row: other_row,
-            col: other_col,
-        } = other;
-        let row_delta = {
-            let max = row.max(other_row);
-            let min = row.min(other_row);
-            max - min
-        };
-        let col_delta = {
-            let max = col.max(other_col);
-            let min = col.min(other_col);
-            max - min
-        };
-        row_delta + col_delta
-    }
-}
-
 const fn const_factor(factor: usize) -> NonZeroUsize {
     match NonZeroUsize::new(factor) {
         Some(v) => v,
-        None =>
-        {
-            #[allow(unconditional_panic)]
-            [][0]
-        }
+        None => panic!("factor must be nonzero"),
     }
 }
 
@@ -242,9 +222,8 @@ impl std::fmt::Display for Galaxies {
 mod tests {
     use std::num::NonZeroUsize;
 
-    use crate::{const_factor, Galaxies, Point, TaxicabDistance};
+    use crate::days::day11_cosmic::{const_factor, Galaxies, Point, FACTOR_TWO};
 
-    const FACTOR_TWO: NonZeroUsize = const_factor(2);
     const FACTOR_TEN: NonZeroUsize = const_factor(10);
     const FACTOR_HUNDRED: NonZeroUsize = const_factor(100);
 
@@ -252,11 +231,11 @@ mod tests {
     fn simple_distance() {
         let p1 = Point { row: 2, col: 7 };
         let p2 = Point { row: 10, col: 1 };
-        assert_eq!(p1.get_distance(p2), 6 + 8);
+        assert_eq!(p1.manhattan_distance(p2), 6 + 8);
 
         // identity distance is 0
-        assert_eq!(p1.get_distance(p1), 0);
-        assert_eq!(p2.get_distance(p2), 0);
+        assert_eq!(p1.manhattan_distance(p1), 0);
+        assert_eq!(p2.manhattan_distance(p2), 0);
     }
 
     fn test_sample_input(factor: NonZeroUsize, expected: usize) {
@@ -277,6 +256,17 @@ mod tests {
         assert_eq!(distances_sum, expected, "factor {factor}");
     }
 
+    #[test]
+    fn parse_error_location() {
+        let Err(err) = Galaxies::new("..#\n.x.") else {
+            panic!("invalid symbol accepted")
+        };
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: unexpected character 'x'"
+        );
+    }
+
     #[test]
     fn sample_input() {
         test_sample_input(FACTOR_TWO, 374);
@@ -1,8 +1,10 @@
 //! Segment-specific analysis functions/types
 
 use super::cache;
-use crate::{DebugParts, Part, Segment};
-use advent_2023::nonempty::NonEmptyVec;
+use crate::days::day12_springs::{DebugParts, Part, Segment};
+use crate::log::{self, Level};
+use crate::log_trace;
+use crate::nonempty::NonEmptyVec;
 use std::num::NonZeroUsize;
 
 impl Segment {
@@ -12,8 +14,11 @@ impl Segment {
         debug_indent: usize,
         cache: &mut Cache,
     ) -> usize {
-        print!("{:width$}", "", width = debug_indent);
-        println!("[Segment::count_possibilities]");
+        log_trace!(
+            "{:width$}[Segment::count_possibilities]",
+            "",
+            width = debug_indent
+        );
         let Some(counts_split) = expected_counts.split_first_copy() else {
             // empty counts, nonempty parts (impossible)
             return 0;
@@ -58,7 +63,7 @@ impl<'a> SegmentAnalysis<'a> {
         (count_first, counts_rest): (NonZeroUsize, &'a [NonZeroUsize]),
         force_left_align: Option<ForceLeftAlign>,
     ) -> Self {
-        println!("{:width$}-> {debug_msg} --v", "", width = self.debug_indent);
+        log_trace!("{:width$}-> {debug_msg} --v", "", width = self.debug_indent);
         Self {
             part_first,
             parts_rest,
@@ -72,12 +77,14 @@ impl<'a> SegmentAnalysis<'a> {
     ///
     /// `Segment` = `Vec<Part>`, and multiple parts can cover one count
     ///
+    /// ```text
     /// |-------------SEGMENT--------------|
     /// |-PART-|-PART-|-PART-|-PART-|-PART-|
     /// ====================================
     /// |--COUNT--| |--COUNT--|  |--COUNT--|
     /// ====================================
     ///            ^-----------^^----Unknowns chosen to be NONE
+    /// ```
     ///
     fn count(self, cache: &mut Cache) -> usize {
         let parts = std::iter::once(self.part_first)
@@ -106,17 +113,16 @@ impl<'a> SegmentAnalysis<'a> {
             debug_indent,
         } = self;
 
-        print!("{:width$}", "", width = debug_indent);
-        print!("* count");
-        print!("(parts: ({part_first:?}, {parts_rest:?})");
-        print!(", counts: ({count_first:?}, {counts_rest:?})");
-        print!(
-            ", {}",
-            force_left_align.as_ref().map_or("none", |_| "Align")
-        );
-        let debug_parts = DebugParts(unsplit_to_vec(part_first, parts_rest));
-        let debug_counts = unsplit_to_vec(count_first, counts_rest);
-        println!(")\tpartial-record {debug_parts} {debug_counts:?}",);
+        if log::enabled(Level::Trace) {
+            let align = force_left_align.as_ref().map_or("none", |_| "Align");
+            let debug_parts = DebugParts(unsplit_to_vec(part_first, parts_rest));
+            let debug_counts = unsplit_to_vec(count_first, counts_rest);
+            eprintln!(
+                "{:width$}* count(parts: ({part_first:?}, {parts_rest:?}), counts: ({count_first:?}, {counts_rest:?}), {align})\tpartial-record {debug_parts} {debug_counts:?}",
+                "",
+                width = debug_indent
+            );
+        }
 
         let (branch, (result, reason)): (_, (usize, &'static str)) =
             if parts_rest.is_empty() && counts_rest.is_empty() {
@@ -136,7 +142,7 @@ impl<'a> SegmentAnalysis<'a> {
                     }
                 }
             };
-        println!(
+        log_trace!(
             "{:width$}{result} <- {reason} ({branch})",
             "",
             width = debug_indent
@@ -368,7 +374,7 @@ impl<'a> SegmentAnalysis<'a> {
                 None => {
                     //else
                     // cannot reduce count, impossible for ON
-                    dbg!(0)
+                    0
                 }
             }
         };
@@ -431,7 +437,7 @@ pub fn unsplit_to_vec<T: Copy>(first: T, rest: &[T]) -> Vec<T> {
 trait SplitFirstCopyOption<T: Copy> {
     fn split_first_copy(&self) -> Option<(T, &[T])>;
 }
-impl<'a, T: Copy> SplitFirstCopyOption<T> for &'a [T] {
+impl<T: Copy> SplitFirstCopyOption<T> for &[T] {
     fn split_first_copy(&self) -> Option<(T, &[T])> {
         self.split_first().map(|(&first, rest)| (first, rest))
     }
@@ -1,8 +1,7 @@
-use advent_2023::{nonempty::NonEmptyVec, vec_nonempty};
-use anyhow::Context;
+use crate::{log_debug, log_trace, nonempty::NonEmptyVec, vec_nonempty, ParseError};
 use std::num::NonZeroUsize;
 
-use crate::{day12_springs::cache, Part, Segment, SegmentBuilder, ONE};
+use crate::days::day12_springs::{cache, Part, Segment, SegmentBuilder, ONE};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Record {
@@ -15,29 +14,45 @@ struct RecordInner {
     known_counts: Vec<NonZeroUsize>,
 }
 impl Record {
-    pub fn parse_lines(input: &str) -> anyhow::Result<Vec<Self>> {
+    pub fn parse_lines(input: &str) -> Result<Vec<Self>, ParseError> {
         input
             .lines()
-            .map(|line| Record::new(line).with_context(|| format!("on line {line:?}")))
+            .enumerate()
+            .map(|(line_index, line)| {
+                Record::new(line).map_err(|err| err.at(line_index, line, line))
+            })
             .collect()
     }
-    pub fn new(line: &str) -> anyhow::Result<Self> {
+    /// Parses the record, with any error [spanning](ParseError::with_span) the offending text
+    pub fn new(line: &str) -> Result<Self, ParseError> {
         const BASE_10: u32 = 10;
 
         let Some((symbols_str, list_str)) = line.split_once(' ') else {
-            anyhow::bail!("space delimiter not found")
+            return Err(ParseError::new("space delimiter not found"));
         };
 
-        println!("------------------------------ symbols={symbols_str:?}");
+        log_trace!("------------------------------ symbols={symbols_str:?}");
+
+        if let Some((index, extra)) = symbols_str
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '.' | '#' | '?'))
+        {
+            return Err(
+                ParseError::unexpected_char(extra).with_span(index..(index + extra.len_utf8()))
+            );
+        }
 
+        let mut count_start = symbols_str.len() + 1;
         let known_counts = list_str
             .split(',')
             .map(|s| {
-                usize::from_str_radix(s, BASE_10)
-                    .map_err(|s| anyhow::anyhow!("invalid number: {s:?}"))
-                    .and_then(|n| {
-                        NonZeroUsize::new(n).ok_or_else(|| anyhow::anyhow!("invalid count: zero"))
-                    })
+                let span = count_start..(count_start + s.len());
+                count_start = span.end + 1;
+                let n = usize::from_str_radix(s, BASE_10).map_err(|err| {
+                    ParseError::new(format!("invalid number: {err}")).with_span(span.clone())
+                })?;
+                NonZeroUsize::new(n)
+                    .ok_or_else(|| ParseError::new("invalid count: zero").with_span(span))
             })
             .collect::<Result<_, _>>()?;
 
@@ -47,7 +62,9 @@ impl Record {
         let mut separator_trailing = false;
 
         let mut first = Some(());
-        while let Some((leading, segment, trailing)) = Segment::new(&mut symbols)? {
+        while let Some((leading, segment, trailing)) =
+            Segment::new(&mut symbols).map_err(|err| ParseError::new(err.to_string()))?
+        {
             if first.take().is_some() {
                 separator_leading = leading.is_some();
             }
@@ -57,7 +74,7 @@ impl Record {
         }
 
         let Some(segments) = NonEmptyVec::new(segments) else {
-            anyhow::bail!("empty segments")
+            return Err(ParseError::new("empty segments").with_span(0..symbols_str.len()));
         };
         Ok(Self {
             inner: RecordInner {
@@ -103,10 +120,8 @@ impl Record {
     pub fn count_possibilities(&self) -> usize {
         let mut caches = Caches::default();
         let result = self.inner.count_possibilities_inner(0, &mut caches);
-        if crate::DEBUG_CACHE {
-            eprintln!("{}", caches.segment.summary("SEGMENT"));
-            eprintln!("{}", caches.part.summary("PART"));
-        }
+        log_debug!("{}", caches.segment.summary("SEGMENT"));
+        log_debug!("{}", caches.part.summary("PART"));
         result
     }
 }
@@ -119,10 +134,11 @@ struct Caches {
 
 impl RecordInner {
     fn count_possibilities_inner(&self, debug_indent: usize, caches: &mut Caches) -> usize {
-        print!("{:width$}", "", width = debug_indent);
         let debug_indent_next = debug_indent + 4;
-        println!(
-            "[Record::count_possibilities] segments={segments:?} known_counts={known_counts:?}",
+        log_trace!(
+            "{:width$}[Record::count_possibilities] segments={segments:?} known_counts={known_counts:?}",
+            "",
+            width = debug_indent,
             segments = self.segments,
             known_counts = self.known_counts
         );
@@ -132,8 +148,7 @@ impl RecordInner {
         if let Some(Impossible) =
             Self::heurestic_segments_impossible((segment_first, segments_rest), &self.known_counts)
         {
-            print!("{:width$}", "", width = debug_indent_next);
-            println!("ignore impossible");
+            log_trace!("{:width$}ignore impossible", "", width = debug_indent_next);
             return 0;
         }
 
@@ -162,9 +177,10 @@ impl RecordInner {
                 if let Some(Impossible) =
                     Self::heurestic_segments_impossible((segment_first, &[]), counts_taken)
                 {
-                    print!("{:width$}", "", width = debug_indent_next);
-                    println!(
-                        "ignore impossible take_count={take_count} of {total}",
+                    log_trace!(
+                        "{:width$}ignore impossible take_count={take_count} of {total}",
+                        "",
+                        width = debug_indent_next,
                         total = self.known_counts.len(),
                     );
                     continue;
@@ -187,8 +203,11 @@ impl RecordInner {
 
             match options {
                 Some(0) => {
-                    print!("{:width$}", "", width = debug_indent);
-                    println!("options = 0 for that run");
+                    log_trace!(
+                        "{:width$}options = 0 for that run",
+                        "",
+                        width = debug_indent
+                    );
                 }
                 None | Some(_) => {
                     if let Some(segments_rest) = NonEmptyVec::new(segments_rest.to_vec()) {
@@ -201,14 +220,20 @@ impl RecordInner {
 
                         let options_num = options.unwrap_or(1);
                         total_options += options_num * options_rest;
-                        print!("{:width$}", "", width = debug_indent);
-                        println!("options += {options_num} * {options_rest} => {total_options}");
+                        log_trace!(
+                            "{:width$}options += {options_num} * {options_rest} => {total_options}",
+                            "",
+                            width = debug_indent
+                        );
                     } else if counts_rest.is_empty() {
                         // no more segments, and satisfied all counts
                         let options_num = options.unwrap_or(0);
                         total_options += options_num;
-                        print!("{:width$}", "", width = debug_indent);
-                        println!("options += {options_num} (no more counts) => {total_options}");
+                        log_trace!(
+                            "{:width$}options += {options_num} (no more counts) => {total_options}",
+                            "",
+                            width = debug_indent
+                        );
                     } else {
                         unreachable!(
                             "ALREADY CHECKED FOR: options not allowed, segments will be empty while counts is nonempty"
@@ -217,8 +242,11 @@ impl RecordInner {
                 }
             }
         }
-        print!("{:width$}", "", width = debug_indent);
-        println!("returning total {total_options}");
+        log_trace!(
+            "{:width$}returning total {total_options}",
+            "",
+            width = debug_indent
+        );
         total_options
     }
     /// Returns `true` if the segments cannot possibly match the known counts
@@ -1,8 +1,8 @@
 use std::num::NonZeroUsize;
 
-use advent_2023::nonempty::NonEmptyVec;
+use crate::nonempty::NonEmptyVec;
 
-use crate::ONE;
+use crate::days::day12_springs::ONE;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Segment(pub NonEmptyVec<Part>);
@@ -1,5 +1,5 @@
-use crate::{day12_springs::cache, Part, Record, Segment};
-use advent_2023::vec_nonempty;
+use crate::days::day12_springs::{cache, Part, Record, Segment};
+use crate::vec_nonempty;
 use std::num::NonZeroUsize;
 
 macro_rules! vec_parts {
@@ -9,14 +9,14 @@ macro_rules! vec_parts {
     (@elem Absolute($value:expr)) => {{
         const VALUE: NonZeroUsize = match NonZeroUsize::new($value) {
             Some(v) => v,
-            None => [][0],
+            None => panic!("nonzero"),
         };
         Part::Absolute(VALUE)
     }};
     (@elem Unknown($value:expr)) => {{
         const VALUE: NonZeroUsize = match NonZeroUsize::new($value) {
             Some(v) => v,
-            None => [][0],
+            None => panic!("nonzero"),
         };
         Part::Unknown(VALUE)
     }};
@@ -72,6 +72,16 @@ fn parse_record_separators() {
     test!("#.##.??. 1", (false, true));
     test!(".#.##.??. 1", (true, true));
 }
+#[test]
+fn parse_error_location() {
+    let err = Record::parse_lines("#.# 1,1\n??.x 2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 4: unexpected character 'x'"
+    );
+    let err = Record::parse_lines("#.# 1,0").unwrap_err();
+    assert_eq!(err.to_string(), "line 1, column 7: invalid count: zero");
+}
 
 fn test_segment_count(symbols: &str, counts: &[usize], expected: usize) {
     let (_lead, segment, _trail) = Segment::new_from_str(symbols)
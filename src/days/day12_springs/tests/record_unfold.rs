@@ -1,5 +1,5 @@
-use crate::{Part, Record, Segment, FACTOR_5};
-use advent_2023::vec_nonempty;
+use crate::days::day12_springs::{Part, Record, Segment, FACTOR_5};
+use crate::vec_nonempty;
 use std::num::NonZeroUsize;
 
 macro_rules! test_record_unfold {
@@ -8,7 +8,7 @@ macro_rules! test_record_unfold {
         original = $original:expr;
         unfolded = $unfolded:expr;
     ) => {{
-        use advent_2023::nonempty::NonEmptyVec;
+        use crate::nonempty::NonEmptyVec;
 
         let symbols: &'static str = $symbols;
         let original: NonEmptyVec<Segment> = $original;
@@ -1,4 +1,4 @@
-use crate::{day12_springs::record::Record, sum_counts, FACTOR_5};
+use crate::days::day12_springs::{record::Record, sum_counts, FACTOR_5};
 
 fn test_record_counts(line: &str, (expected, expect_unfolded): (usize, usize)) {
     let record = Record::new(line).expect("valid line");
@@ -1,15 +1,28 @@
-use advent_2023::{dimension::Dimension, either::Either, point::Point};
+use crate::{
+    dimension::Dimension,
+    grid::{Grid, View},
+    log::{self, Level},
+    log_trace, ParseError,
+};
 
-fn main() -> anyhow::Result<()> {
-    println!("hello, where are the mirrors?");
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 13;
+    type Parsed = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let input = advent_2023::get_input_string()?;
-
-    let Stats { summary_sum } = eval_input(&input, Some(1))?;
-
-    println!("Sum of reflection notes: {summary_sum}");
-
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(input.to_owned())
+    }
+    fn part1(input: &Self::Parsed) -> anyhow::Result<usize> {
+        let Stats { summary_sum } = eval_input(input, None)?;
+        Ok(summary_sum)
+    }
+    fn part2(input: &Self::Parsed) -> anyhow::Result<usize> {
+        let Stats { summary_sum } = eval_input(input, Some(1))?;
+        Ok(summary_sum)
+    }
 }
 
 struct Stats {
@@ -18,114 +31,54 @@ struct Stats {
 
 fn eval_input(input: &str, goal_error_count: Option<usize>) -> anyhow::Result<Stats> {
     let mut summary_sum = 0;
-    let mut lines = input.lines();
+    let mut lines = input.lines().enumerate();
     loop {
-        let Some(mut grid) = Grid::new(lines.by_ref())? else {
+        let Some(mut pattern) = Pattern::new(lines.by_ref())? else {
             break;
         };
         if let Some(goal_error_count) = goal_error_count {
-            grid.goal_error_count = goal_error_count;
+            pattern.goal_error_count = goal_error_count;
         }
-        summary_sum += grid.summarize_reflection();
+        summary_sum += pattern.summarize_reflection();
     }
     Ok(Stats { summary_sum })
 }
 
-struct Grid {
-    cells: Vec<Cell>,
-    width: usize,
-    max_row: usize,
+struct Pattern {
+    grid: Grid<Cell>,
     goal_error_count: usize,
 }
-impl Grid {
+impl Pattern {
     #[allow(dead_code)] // for tests
     fn new_from_str(input: &str) -> anyhow::Result<Option<Self>> {
-        let mut lines = input.lines();
+        let mut lines = input.lines().enumerate();
         let result = Self::new(lines.by_ref());
         assert_eq!(lines.next(), None);
         result
     }
-    fn new<'a>(lines: impl Iterator<Item = &'a str>) -> anyhow::Result<Option<Self>> {
-        let mut cells = vec![];
-        let mut width = None;
-        let mut max_row = 0;
-        for line in lines {
-            if line.is_empty() {
-                break;
-            }
-            let current_width = line.len();
-            match width {
-                None => {
-                    width = Some(current_width);
-                }
-                Some(existing) if current_width != existing => {
-                    anyhow::bail!("invalid width {current_width}, expected width {existing}")
-                }
-                Some(_match) => {}
-            }
-
-            let row = line
-                .chars()
-                .map(Cell::try_from)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|extra| anyhow::anyhow!("unknown character {extra:?}"))?;
-            cells.extend(row);
-            max_row += 1;
-        }
-        Ok(width.map(|width| Self {
-            cells,
-            width,
-            max_row,
+    /// Parses lines until the first blank line, with each line's index in the input
+    fn new<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> anyhow::Result<Option<Self>> {
+        let grid = Grid::parse_lines(lines, Cell::try_from)?;
+        Ok(grid.map(|grid| Self {
+            grid,
             goal_error_count: 0,
         }))
     }
-    fn row(&self, row: usize) -> Option<&[Cell]> {
-        let start = Point { row, col: 0 }
-            .index_for_width(self.width)
-            .expect("col 0 within nonzero width");
-        let end = Point {
-            row: row + 1,
-            col: 0,
-        }
-        .index_for_width(self.width)
-        .expect("col 0 within nonzero width");
-        self.cells.get(start..end)
-    }
-    fn col(&self, col: usize) -> impl Iterator<Item = Cell> + Clone + '_ {
-        if col >= self.width {
-            Either::A(std::iter::empty())
-        } else {
-            Either::B(ColIter {
-                cells: &self.cells,
-                width: self.width,
-                col,
-                next_row: Some(0),
-            })
-        }
-    }
-    fn series(&self, dimension: Dimension, n: usize) -> impl Iterator<Item = Cell> + Clone + '_ {
-        match dimension {
-            Dimension::Row => {
-                let row = self.row(n).unwrap_or(&[]).iter().copied();
-                Either::A(row)
-            }
-            Dimension::Col => Either::B(self.col(n)),
-        }
-    }
-    fn size(&self, dimension: Dimension) -> usize {
+    /// Returns a view where each series along the dimension is a row
+    fn oriented(&self, dimension: Dimension) -> View<'_, Cell> {
         match dimension {
-            Dimension::Row => self.max_row,
-            Dimension::Col => self.width,
+            Dimension::Row => self.grid.view(),
+            Dimension::Col => self.grid.view().transpose(),
         }
     }
     /// Returns `Some(true)` if all cases matched, `Some(false)` if a contradiction is found,
     /// or `None` if no comparison could be performed
     fn get_error_count(&self, dimension: Dimension, (a, b): (usize, usize)) -> Option<usize> {
-        const DEBUG: bool = false;
-
-        let mut a_iter = self.series(dimension, a);
-        let mut b_iter = self.series(dimension, b);
-        let (a_debug, b_debug) = if DEBUG {
+        let view = self.oriented(dimension);
+        let mut a_iter = view.row(a).copied();
+        let mut b_iter = view.row(b).copied();
+        let debug = log::enabled(Level::Trace);
+        let (a_debug, b_debug) = if debug {
             let a_debug = a_iter.clone().collect::<Vec<_>>();
             let b_debug = b_iter.clone().collect::<Vec<_>>();
             (a_debug, b_debug)
@@ -147,8 +100,8 @@ impl Grid {
                 (None, Some(_)) | (Some(_), None) => {}
             }
         }
-        if DEBUG {
-            println!("\terrors={error_count:?} {dimension:?} ({a},{b}): {a_debug:?}; {b_debug:?}");
+        if debug {
+            eprintln!("\terrors={error_count:?} {dimension:?} ({a},{b}): {a_debug:?}; {b_debug:?}");
         }
         error_count
     }
@@ -178,16 +131,17 @@ impl Grid {
                 }
             }
         }
-        println!("reflection? {dimension:?} pivot {pivot}: {matched:?}");
+        log_trace!("reflection? {dimension:?} pivot {pivot}: {matched:?}");
         matched
     }
     fn summarize_reflection(&self) -> usize {
-        assert!(self.max_row % 2 == 1, "odd rows {}", self.max_row);
-        assert!(self.width % 2 == 1, "odd cols {}", self.width);
+        let (width, height) = (self.grid.width(), self.grid.height());
+        assert!(height % 2 == 1, "odd rows {height}");
+        assert!(width % 2 == 1, "odd cols {width}");
 
         // analyze ROWS
         for dimension in [Dimension::Row, Dimension::Col] {
-            for pivot in 0..self.size(dimension) {
+            for pivot in 0..self.oriented(dimension).height() {
                 if let Some(true) = self.is_reflection(dimension, pivot) {
                     let code = match dimension {
                         Dimension::Row => (pivot + 1) * 100,
@@ -201,41 +155,18 @@ impl Grid {
     }
 }
 
-#[derive(Clone)]
-struct ColIter<'a> {
-    cells: &'a [Cell],
-    width: usize,
-    col: usize,
-    next_row: Option<usize>,
-}
-impl Iterator for ColIter<'_> {
-    type Item = Cell;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let row = self.next_row?;
-        let elem = {
-            let point = Point { row, col: self.col };
-            let index = point.index_for_width(self.width)?;
-            self.cells.get(index).copied()
-        };
-
-        self.next_row = elem.is_some().then_some(row + 1);
-        elem
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Cell {
     Ash,
     Rock,
 }
 impl TryFrom<char> for Cell {
-    type Error = char;
+    type Error = ParseError;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Ash),
             '#' => Ok(Self::Rock),
-            extra => Err(extra),
+            extra => Err(ParseError::unexpected_char(extra)),
         }
     }
 }
@@ -251,7 +182,7 @@ impl std::fmt::Debug for Cell {
 
 #[cfg(test)]
 mod tests {
-    use crate::{eval_input, Cell, Dimension, Grid};
+    use crate::days::day13_mirrors::{eval_input, Cell, Dimension, Pattern};
 
     #[test]
     fn finds_cols() {
@@ -262,10 +193,10 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.";
-        let grid = Grid::new_from_str(input)
+        let pattern = Pattern::new_from_str(input)
             .expect("valid text")
             .expect("nonempty");
-        let col_8 = grid.col(8).collect::<Vec<_>>();
+        let col_8 = pattern.grid.col(8).copied().collect::<Vec<_>>();
         assert_eq!(
             col_8,
             vec![
@@ -279,7 +210,7 @@ mod tests {
             ]
         );
 
-        assert_eq!(grid.is_reflection(Dimension::Col, 4), Some(true));
+        assert_eq!(pattern.is_reflection(Dimension::Col, 4), Some(true));
     }
 
     #[test]
@@ -1,179 +1,197 @@
-use std::collections::HashMap;
+use crate::{
+    cycle, dimension::Dimension, grid::Grid, log_trace, nonempty::NonEmptyVec, point::Point,
+    ParseError, RuleArgs,
+};
 
-use advent_2023::{dimension::Dimension, either::Either, point::Point};
+pub use self::{animate::animate, packed::PackedPlatform};
 
-fn main() -> anyhow::Result<()> {
-    println!("hello, turns out some ROCKS will ROLL");
+mod animate;
+mod packed;
 
-    let input = advent_2023::get_input_string()?;
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 14;
+    type Parsed = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let Stats {
-        sum_round_weight,
-        sum_after_one_million,
-    } = eval_input(&input)?;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Self::parse_with_rules(input, &RuleArgs::default())
+    }
+    fn parse_with_rules(input: &str, rules: &RuleArgs) -> anyhow::Result<Self::Parsed> {
+        Ok(Puzzle {
+            platforms: parse_grids(input)?,
+            rules: Rules::new(rules)?,
+        })
+    }
+    fn part1(puzzle: &Self::Parsed) -> anyhow::Result<usize> {
+        Ok(sum_round_weight(&puzzle.platforms, &puzzle.rules))
+    }
+    fn part2(puzzle: &Self::Parsed) -> anyhow::Result<usize> {
+        Ok(sum_after_one_million(&puzzle.platforms, &puzzle.rules))
+    }
+}
 
-    println!("Sum of round weights: {sum_round_weight}");
-    println!("Sum of after 1 million... {sum_after_one_million}");
+/// Platforms of the input, with the rules for tilting them
+pub struct Puzzle {
+    platforms: Vec<Platform>,
+    rules: Rules,
+}
 
-    Ok(())
+/// Tilts of each spin cycle, and the edge for measuring the load
+///
+/// Defaults to the puzzle's rules: tilting north, west, south then east, measuring against north.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    sequence: TiltSequence,
+    edge: Direction,
+}
+impl Rules {
+    pub fn new(args: &RuleArgs) -> anyhow::Result<Self> {
+        let RuleArgs { sequence, edge } = args;
+        let sequence = match sequence {
+            Some(sequence) => sequence.parse()?,
+            None => TiltSequence::default(),
+        };
+        let edge = match edge.as_deref() {
+            Some(edge) => match edge.chars().collect::<Vec<_>>()[..] {
+                [letter] => Direction::from_letter(letter)?,
+                _ => anyhow::bail!("expected a single letter edge (N, W, S or E), found {edge:?}"),
+            },
+            None => Direction::default(),
+        };
+        Ok(Self { sequence, edge })
+    }
 }
 
+/// Directions of the tilts in one spin cycle, repeated in order
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TiltSequence(NonEmptyVec<Direction>);
+impl TiltSequence {
+    /// Returns the direction of the tilt at the step, counting from zero
+    fn direction_at(&self, step: usize) -> Direction {
+        self.0[step % self.0.len()]
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+impl Default for TiltSequence {
+    fn default() -> Self {
+        Self(crate::vec_nonempty![
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ])
+    }
+}
+impl std::str::FromStr for TiltSequence {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let directions = s
+            .chars()
+            .map(Direction::from_letter)
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(directions) = NonEmptyVec::new(directions) else {
+            anyhow::bail!("empty tilt sequence")
+        };
+        Ok(Self(directions))
+    }
+}
+
+#[cfg(test)]
 struct Stats {
     sum_round_weight: usize,
     sum_after_one_million: usize,
 }
 
+#[cfg(test)]
 fn eval_input(input: &str) -> anyhow::Result<Stats> {
-    let mut sum_round_weight = 0;
-    let mut sum_after_one_million = 0;
-    let mut lines = input.lines();
-    loop {
-        let Some(grid) = Grid::new(lines.by_ref())? else {
-            break;
-        };
-        let mut single_roll = grid.clone();
-        single_roll.roll_stones(Direction::North);
-        sum_round_weight += single_roll.get_round_weight();
-
-        let after_million = grid.perform_cycles(1_000_000_000);
-        sum_after_one_million += after_million.get_round_weight();
-    }
+    let grids = parse_grids(input)?;
+    let rules = Rules::default();
     Ok(Stats {
-        sum_round_weight,
-        sum_after_one_million,
+        sum_round_weight: sum_round_weight(&grids, &rules),
+        sum_after_one_million: sum_after_one_million(&grids, &rules),
     })
 }
 
+pub fn parse_grids(input: &str) -> anyhow::Result<Vec<Platform>> {
+    let mut grids = vec![];
+    let mut lines = input.lines().enumerate();
+    while let Some(grid) = Platform::new(lines.by_ref())? {
+        grids.push(grid);
+    }
+    Ok(grids)
+}
+
+/// Total load after the first tilt of the sequence
+fn sum_round_weight(grids: &[Platform], rules: &Rules) -> usize {
+    grids
+        .iter()
+        .map(|grid| {
+            let mut single_roll = PackedPlatform::from(grid);
+            single_roll.roll_stones(*rules.sequence.0.first());
+            single_roll.get_load(rules.edge)
+        })
+        .sum()
+}
+
+fn sum_after_one_million(grids: &[Platform], rules: &Rules) -> usize {
+    load_after_cycles_packed(grids, rules, 1_000_000_000)
+}
+
+/// Total load after the spin cycles, tilting the grid of cells one rock at a time
+///
+/// Kept as the reference for [`load_after_cycles_packed`], and for comparing their speed.
+pub fn load_after_cycles(grids: &[Platform], rules: &Rules, cycles: usize) -> usize {
+    grids
+        .iter()
+        .map(|grid| {
+            grid.clone()
+                .perform_cycles(rules, cycles)
+                .get_load(rules.edge)
+        })
+        .sum()
+}
+
+/// Total load after the spin cycles, tilting bit-packed lines of rocks
+pub fn load_after_cycles_packed(grids: &[Platform], rules: &Rules, cycles: usize) -> usize {
+    grids
+        .iter()
+        .map(|grid| {
+            PackedPlatform::from(grid)
+                .perform_cycles(&rules.sequence, cycles)
+                .get_load(rules.edge)
+        })
+        .sum()
+}
+
 #[derive(Clone, PartialEq, Eq, Hash)]
-struct Grid {
-    cells: Vec<Cell>,
-    width: usize,
-    max_row: usize,
-    random_direction_for_cache_key: Option<Direction>,
+pub struct Platform {
+    grid: Grid<Cell>,
 }
-impl Grid {
+impl Platform {
     #[allow(dead_code)] // for tests
     fn new_from_str(input: &str) -> anyhow::Result<Option<Self>> {
-        let mut lines = input.lines();
+        let mut lines = input.lines().enumerate();
         let result = Self::new(lines.by_ref());
         assert_eq!(lines.next(), None);
         result
     }
-    fn new<'a>(lines: impl Iterator<Item = &'a str>) -> anyhow::Result<Option<Self>> {
-        let mut cells = vec![];
-        let mut width = None;
-        let mut max_row = 0;
-        for line in lines {
-            if line.is_empty() {
-                break;
-            }
-            let current_width = line.len();
-            match width {
-                None => {
-                    width = Some(current_width);
-                }
-                Some(existing) if current_width != existing => {
-                    anyhow::bail!("invalid width {current_width}, expected width {existing}")
-                }
-                Some(_match) => {}
-            }
-
-            let row = line
-                .chars()
-                .map(Cell::try_from)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|extra| anyhow::anyhow!("unknown character {extra:?}"))?;
-            cells.extend(row);
-            max_row += 1;
-        }
-        Ok(width.map(|width| Self {
-            cells,
-            width,
-            max_row,
-            random_direction_for_cache_key: None,
-        }))
-    }
-    fn iter(&self) -> impl Iterator<Item = (Point, Cell)> + '_ {
-        Iter {
-            cells: &self.cells,
-            width: self.width,
-            next_index: Some(0),
-        }
-    }
-    fn row_range(&self, row: usize) -> std::ops::Range<usize> {
-        assert!(row <= self.max_row, "row {row}");
-        let start = Point { row, col: 0 }
-            .index_for_width(self.width)
-            .expect("col 0 within nonzero width");
-        let end = Point {
-            row: row + 1,
-            col: 0,
-        }
-        .index_for_width(self.width)
-        .expect("col 0 within nonzero width");
-        start..end
-    }
-    fn row(&self, row: usize) -> Option<&[Cell]> {
-        self.cells.get(self.row_range(row))
-    }
-    // fn col(&self, col: usize) -> impl Iterator<Item = Cell> + Clone + '_ {
-    //     if col >= self.width {
-    //         Either::A(std::iter::empty())
-    //     } else {
-    //         Either::B(ColIter {
-    //             cells: &self.cells,
-    //             width: self.width,
-    //             col,
-    //             next_row: Some(0),
-    //         })
-    //     }
-    // }
-    fn col_indices(&self, col: usize) -> impl Iterator<Item = usize> + Clone {
-        assert!(
-            col < self.width,
-            "col {col}, width {width}",
-            width = self.width
-        );
-        if col >= self.width {
-            Either::A(std::iter::empty())
-        } else {
-            Either::B(ColIterIndex {
-                len: self.cells.len(),
-                width: self.width,
-                col,
-                next_row: Some(0),
-            })
-        }
-    }
-    // fn series(&self, dimension: Dimension, n: usize) -> impl Iterator<Item = Cell> + Clone + '_ {
-    //     match dimension {
-    //         Dimension::Row => {
-    //             let row = self.row(n).unwrap_or(&[]).iter().copied();
-    //             Either::A(row)
-    //         }
-    //         Dimension::Col => Either::B(self.col(n)),
-    //     }
-    // }
-    fn indices(&self, dimension: Dimension, n: usize) -> impl Iterator<Item = usize> + Clone {
-        match dimension {
-            Dimension::Row => Either::A(self.row_range(n)),
-            Dimension::Col => Either::B(self.col_indices(n)),
-        }
-    }
-    fn size(&self, dimension: Dimension) -> usize {
-        match dimension {
-            Dimension::Row => self.max_row,
-            Dimension::Col => self.width,
-        }
+    /// Parses lines until the first blank line, with each line's index in the input
+    fn new<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> anyhow::Result<Option<Self>> {
+        let grid = Grid::parse_lines(lines, Cell::try_from)?;
+        Ok(grid.map(|grid| Self { grid }))
     }
     fn roll_stones(&mut self, direction: Direction) {
         let (roll_dim, roll_step) = direction.roll_dim();
         // dbg!(("ROLLING", direction, roll_dim, roll_step));
 
-        let major_axis = self.size(roll_dim);
+        let major_axis = self.grid.size(roll_dim);
         for major in 0..major_axis {
-            let elem_indices = self.indices(roll_dim, major);
-            let mut elems: Vec<_> = elem_indices.clone().map(|i| self.cells[i]).collect();
+            let elem_points = self.grid.series_points(roll_dim, major);
+            let mut elems: Vec<_> = elem_points.clone().map(|point| self.grid[point]).collect();
             let elems_len = elems.len();
 
             // let DEBUG_elems = elems.clone();
@@ -209,163 +227,42 @@ impl Grid {
             }
             // dbg!("changed", major, &DEBUG_elems, &elems);
 
-            for (i, elem) in elem_indices.zip(elems) {
-                self.cells[i] = elem;
+            for (point, elem) in elem_points.zip(elems) {
+                self.grid[point] = elem;
             }
         }
     }
-    fn get_round_weight(&self) -> usize {
-        let mut sum = 0;
-        for (point, cell) in self.iter() {
-            if cell == Cell::Some(Rock::Round) {
-                let weight = self.max_row - point.row;
-                sum += weight;
-            }
-        }
-        sum
+    /// Returns the total load on the supports at the edge, where each round rock weighs its
+    /// distance from the opposite edge
+    fn get_load(&self, edge: Direction) -> usize {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        self.grid
+            .iter()
+            .filter(|(_, &cell)| cell == Cell::Some(Rock::Round))
+            .map(|(point, _)| edge.load_weight(point, (width, height)))
+            .sum()
     }
-    fn perform_cycles(self, total_cycles_count: usize) -> Self {
-        // NOTE: A cycle is defined as 4 individual steps
-        let steps_count = 4 * total_cycles_count;
-        self.perform_steps(steps_count)
-    }
-    fn perform_steps(self, steps_count: usize) -> Self {
-        let reference_clone = {
-            let mut clone = self.clone();
-            clone.random_direction_for_cache_key = Some(Direction::East);
-            clone
-        };
-        self.perform_steps_inner(steps_count, Some(reference_clone), None)
+    fn perform_cycles(self, rules: &Rules, total_cycles_count: usize) -> Self {
+        // NOTE: A cycle is one tilt for each direction in the sequence
+        let steps_count = rules.sequence.len() * total_cycles_count;
+        self.perform_steps(rules, steps_count)
     }
-    fn perform_steps_inner(
-        mut self,
-        total_count: usize,
-        find_cycle: Option<Self>,
-        start_direction: Option<Direction>,
-    ) -> Self {
-        let mut past_cases = find_cycle.map(|original| {
-            let mut map = HashMap::new();
-            map.insert(original, 0);
-            map
+    fn perform_steps(self, rules: &Rules, steps_count: usize) -> Self {
+        let Rules { sequence, edge } = rules;
+        // NOTE: the position in the sequence is part of the state, so repeats are in the same phase
+        let (platform, _) = cycle::nth_state((self, 0), steps_count, |(platform, index)| {
+            let direction = sequence.direction_at(*index);
+            log_trace!("{}\t{direction:?}", platform.get_load(*edge));
+            let mut platform = platform.clone();
+            platform.roll_stones(direction);
+            (platform, (index + 1) % sequence.len())
         });
-        let mut direction = start_direction.unwrap_or_default();
-        for count_index in 0..total_count {
-            println!(
-                "{count_index:9}\t{}\t{direction:?}",
-                self.get_round_weight()
-            );
-            self.roll_stones(direction);
-            self.random_direction_for_cache_key = Some(direction);
-            if let Some(past_cases) = &mut past_cases {
-                if let Some(past_count_index) = past_cases.get(&self) {
-                    let cycle_length = count_index - past_count_index;
-                    let remaining = (total_count - past_count_index - 1) % cycle_length;
-                    dbg!(
-                        "DETECTED CYCLE!!!",
-                        count_index,
-                        past_count_index,
-                        cycle_length,
-                        remaining
-                    );
-                    return self.perform_steps_inner(remaining, None, Some(direction.next()));
-                } else {
-                    past_cases.insert(self.clone(), count_index);
-                }
-            }
-            direction = direction.next();
-        }
-        // dbg!(&self, self.get_round_weight());
-        self
+        platform
     }
 }
-impl std::fmt::Debug for Grid {
+impl std::fmt::Debug for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Grid:")?;
-        if let Some(direction) = &self.random_direction_for_cache_key {
-            write!(f, "\t(last {direction:?})")?;
-        }
-        write!(f, "\n  ")?;
-        for col in 0..self.width {
-            write!(f, "{}", col % 10)?;
-        }
-        writeln!(f)?;
-
-        for row in 0..self.max_row {
-            write!(f, "{} ", row % 10)?;
-            let Some(cells) = self.row(row) else {
-                writeln!(f)?;
-                break;
-            };
-            for cell in cells {
-                write!(f, "{cell:?}")?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
-}
-
-#[derive(Clone)]
-struct ColIter<'a> {
-    cells: &'a [Cell],
-    width: usize,
-    col: usize,
-    next_row: Option<usize>,
-}
-impl Iterator for ColIter<'_> {
-    type Item = Cell;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let row = self.next_row?;
-        let elem = {
-            let point = Point { row, col: self.col };
-            let index = point.index_for_width(self.width)?;
-            self.cells.get(index).copied()
-        };
-
-        self.next_row = elem.is_some().then_some(row + 1);
-        elem
-    }
-}
-
-#[derive(Clone)]
-struct ColIterIndex {
-    len: usize,
-    width: usize,
-    col: usize,
-    next_row: Option<usize>,
-}
-impl Iterator for ColIterIndex {
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let row = self.next_row?;
-        let index = {
-            let point = Point { row, col: self.col };
-            let index = point.index_for_width(self.width)?;
-            (index < self.len).then_some(index)
-        };
-
-        self.next_row = index.is_some().then_some(row + 1);
-        index
-    }
-}
-
-#[derive(Clone)]
-struct Iter<'a> {
-    cells: &'a [Cell],
-    width: usize,
-    next_index: Option<usize>,
-}
-impl Iterator for Iter<'_> {
-    type Item = (Point, Cell);
-    fn next(&mut self) -> Option<Self::Item> {
-        let index = self.next_index.take()?;
-        let point = Point::from_index_width(index, self.width);
-        let cell = self.cells.get(index).copied()?;
-
-        self.next_index = Some(index + 1);
-        Some((point, cell))
+        write!(f, "Grid:\n{:?}", self.grid)
     }
 }
 
@@ -381,13 +278,13 @@ enum Rock {
     Cube,
 }
 impl TryFrom<char> for Cell {
-    type Error = char;
+    type Error = ParseError;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::None),
             'O' => Ok(Self::Some(Rock::Round)),
             '#' => Ok(Self::Some(Rock::Cube)),
-            extra => Err(extra),
+            extra => Err(ParseError::unexpected_char(extra)),
         }
     }
 }
@@ -434,19 +331,35 @@ impl Direction {
     //     roll.other()
     // }
 
-    fn next(self) -> Self {
+    fn from_letter(letter: char) -> anyhow::Result<Self> {
+        match letter.to_ascii_uppercase() {
+            'N' => Ok(Self::North),
+            'W' => Ok(Self::West),
+            'S' => Ok(Self::South),
+            'E' => Ok(Self::East),
+            _ => anyhow::bail!("invalid direction {letter:?}, expected N, W, S or E"),
+        }
+    }
+    /// Returns the weight of a round rock at the point, for the load on this edge
+    fn load_weight(self, point: Point, (width, height): (usize, usize)) -> usize {
         match self {
-            Self::North => Self::West,
-            Self::West => Self::South,
-            Self::South => Self::East,
-            Self::East => Self::North,
+            Self::North => height - point.row,
+            Self::South => point.row + 1,
+            Self::West => width - point.col,
+            Self::East => point.col + 1,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{eval_input, Direction, Grid};
+    use crate::{
+        days::day14_rocks_roll::{
+            eval_input, load_after_cycles, parse_grids, Direction, Platform, Rules, Solution,
+            TiltSequence,
+        },
+        RuleArgs, Solver,
+    };
 
     macro_rules! grid {
         ($($line:expr),+ $(,)? $(; $direction:expr)?) => {{
@@ -456,14 +369,13 @@ mod tests {
                     line
                 }),+
             ];
-            #[allow(unused_mut)]
-            let mut grid = Grid::new(lines.into_iter())
-                .expect("valid input")
-                .expect("nonempty by macro construction");
+            // the optional direction notes the last roll, for the reader
             $(
-                grid.random_direction_for_cache_key = Some($direction);
+                let _: Direction = $direction;
             )?
-            grid
+            Platform::new(lines.into_iter().enumerate())
+                .expect("valid input")
+                .expect("nonempty by macro construction")
         }};
     }
 
@@ -473,10 +385,10 @@ mod tests {
             roll $direction:expr;
             expected=$expected:expr;
         ) => {{
-            let original: Grid = $original;
+            let original: Platform = $original;
             dbg!(&original);
             let direction: Direction = $direction;
-            let expected: Grid = $expected;
+            let expected: Platform = $expected;
             dbg!(&expected);
             let modified = {
                 let mut modified = original.clone();
@@ -495,12 +407,12 @@ mod tests {
                 $count:expr => $expected:expr;
             )+
         ) => {{
-            let original: Grid = $original;
+            let original: Platform = $original;
             // dbg!(&original);
             $(
                 let count: usize = $count;
-                let expected: Grid = $expected;
-                let modified = original.clone().perform_steps(count);
+                let expected: Platform = $expected;
+                let modified = original.clone().perform_steps(&Rules::default(), count);
                 assert_eq!(modified, expected, "for count {count:?}");
             )+
         }};
@@ -600,9 +512,9 @@ mod tests {
     #[test]
     fn counts_round_weight_north() {
         let one = grid! {"O"};
-        assert_eq!(one.get_round_weight(), 1);
+        assert_eq!(one.get_load(Direction::North), 1);
         let five = grid! {"OOOOO"};
-        assert_eq!(five.get_round_weight(), 5);
+        assert_eq!(five.get_load(Direction::North), 5);
         let also_five = grid! {
             "O..",
             "...",
@@ -610,7 +522,7 @@ mod tests {
             "...",
             "...",
         };
-        assert_eq!(also_five.get_round_weight(), 5);
+        assert_eq!(also_five.get_load(Direction::North), 5);
 
         let mut lots = grid! {
             "O...",
@@ -618,9 +530,9 @@ mod tests {
             "..O.",
             "...O",
         };
-        assert_eq!(lots.get_round_weight(), 4 + 3 + 2 + 1);
+        assert_eq!(lots.get_load(Direction::North), 4 + 3 + 2 + 1);
         lots.roll_stones(Direction::North);
-        assert_eq!(lots.get_round_weight(), 4 * 4);
+        assert_eq!(lots.get_load(Direction::North), 4 * 4);
     }
 
     #[test]
@@ -652,7 +564,7 @@ mod tests {
                 "#....#....",
             };
         };
-        assert_eq!(grid.get_round_weight(), 136);
+        assert_eq!(grid.get_load(Direction::North), 136);
     }
     #[test]
     fn sample_input_north() {
@@ -687,6 +599,87 @@ O.#..O.#.#
         assert_eq!(stats.sum_after_one_million, 64);
     }
 
+    const SAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    fn rules(sequence: &str, edge: &str) -> RuleArgs {
+        RuleArgs {
+            sequence: Some(sequence.to_owned()),
+            edge: Some(edge.to_owned()),
+        }
+    }
+
+    #[test]
+    fn parses_tilt_sequence() {
+        assert_eq!(
+            "nwSE".parse::<TiltSequence>().unwrap(),
+            TiltSequence::default()
+        );
+        let sequence: TiltSequence = "NNES".parse().unwrap();
+        let directions: Vec<_> = (0..5).map(|step| sequence.direction_at(step)).collect();
+        assert_eq!(
+            directions,
+            [
+                Direction::North,
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::North
+            ]
+        );
+        assert_eq!(
+            "".parse::<TiltSequence>().unwrap_err().to_string(),
+            "empty tilt sequence"
+        );
+        assert_eq!(
+            "NX".parse::<TiltSequence>().unwrap_err().to_string(),
+            "invalid direction 'X', expected N, W, S or E"
+        );
+        assert!(Rules::new(&rules("N", "NS")).is_err());
+        assert_eq!(Rules::new(&RuleArgs::default()).unwrap(), Rules::default());
+    }
+
+    #[test]
+    fn loads_against_each_edge() {
+        let grid = grid! {
+            "OO.#",
+            "....",
+            "...O",
+        };
+        assert_eq!(grid.get_load(Direction::North), 3 + 3 + 1);
+        assert_eq!(grid.get_load(Direction::South), 1 + 1 + 3);
+        assert_eq!(grid.get_load(Direction::West), 4 + 3 + 1);
+        assert_eq!(grid.get_load(Direction::East), 1 + 2 + 4);
+    }
+
+    #[test]
+    fn sample_input_with_rules() {
+        // tilting north forever settles after the first tilt
+        let puzzle = Solution::parse_with_rules(SAMPLE, &rules("N", "N")).unwrap();
+        assert_eq!(Solution::part1(&puzzle).unwrap(), 136);
+        assert_eq!(Solution::part2(&puzzle).unwrap(), 136);
+
+        let args = rules("NNES", "s");
+        let puzzle = Solution::parse_with_rules(SAMPLE, &args).unwrap();
+        let expected = load_after_cycles(
+            &parse_grids(SAMPLE).unwrap(),
+            &Rules::new(&args).unwrap(),
+            1_000_000_000,
+        );
+        assert_eq!(Solution::part2(&puzzle).unwrap(), expected);
+
+        assert!(Solution::parse_with_rules(SAMPLE, &rules("NWSEQ", "N")).is_err());
+    }
+
     #[test]
     fn sample_input_and_another_north() {
         let input = "O....#....
@@ -1324,7 +1317,7 @@ OOOO
         //     "#...O###.O",
         //     "#.OOO#..OO"; Direction::East
         // };
-        assert_eq!(state24_step23.get_round_weight(), 64);
+        assert_eq!(state24_step23.get_load(Direction::North), 64);
     }
 
     #[test]
@@ -1435,8 +1428,12 @@ O.";
     }
     #[test]
     fn dimension_bounds() {
-        let mut direction = Direction::North;
-        for _ in 0..4 {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
             let mut tall = grid! {
                 "...",
                 "...",
@@ -1453,9 +1450,6 @@ O.";
                 ".......................",
             };
             wide.roll_stones(direction);
-
-            // advance
-            direction = direction.next();
         }
     }
 
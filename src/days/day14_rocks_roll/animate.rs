@@ -0,0 +1,521 @@
+//! Interactive terminal view of the platform, tilting one direction at a time
+//!
+//! Keys: `space` pauses, `n`/`→` and `p`/`←` step while paused, `r` reverses the playback,
+//! `+`/`-` change the speed, and `q` moves on to the next platform (or quits after the last).
+
+use super::{parse_grids, Cell, Direction, Platform, Rock, Rules};
+use crate::{cycle::Cycle, RuleArgs};
+use anyhow::Context;
+use console::{Key, Style, Term};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    mem::MaybeUninit,
+    time::Duration,
+};
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(250);
+const MIN_DELAY: Duration = Duration::from_millis(15);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Animates each platform in the input on the terminal, until quit
+pub fn animate(input: &str, rules: &RuleArgs) -> anyhow::Result<()> {
+    let platforms = parse_grids(input)?;
+    let rules = Rules::new(rules)?;
+    let term = Term::stdout();
+    if !term.is_term() {
+        anyhow::bail!("animation requires a terminal on stdout")
+    }
+    let mut keys = RawKeys::new().context("animation requires a terminal on stdin")?;
+    let _cursor = HiddenCursor::new(&term)?;
+    term.clear_screen()?;
+
+    let platforms_count = platforms.len();
+    for (index, platform) in platforms.into_iter().enumerate() {
+        let mut animation = Animation::new(platform, rules.clone());
+        loop {
+            let title = format!("Platform {}/{platforms_count}", index + 1);
+            draw(&term, &animation.frame(&title))?;
+
+            let timeout = animation.playing.then_some(animation.delay);
+            let flow = match keys.read_key(timeout) {
+                Ok(Some(key)) => animation.handle(key),
+                Ok(None) => {
+                    if animation.playing {
+                        animation.tick();
+                    }
+                    Flow::Continue
+                }
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Flow::Quit,
+                Err(err) => return Err(err.into()),
+            };
+            match flow {
+                Flow::Continue => {}
+                Flow::Next => break,
+                Flow::Quit => return Ok(()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes each line at its own row, over the previous frame
+fn draw(term: &Term, lines: &[String]) -> std::io::Result<()> {
+    for (row, line) in lines.iter().enumerate() {
+        term.move_cursor_to(0, row)?;
+        term.clear_line()?;
+        term.write_str(line)?;
+    }
+    term.move_cursor_to(0, lines.len())?;
+    term.clear_to_end_of_screen()
+}
+
+/// Reads keys from stdin with a timeout, restoring the terminal settings when dropped
+///
+/// Echo, line editing and signals are off meanwhile, so `Ctrl-C` arrives as a key and quits
+/// through the same path as the others, rather than exiting with the terminal still raw.
+struct RawKeys {
+    saved: libc::termios,
+    /// Bytes read but not yet decoded, as one read may hold several keys
+    pending: VecDeque<u8>,
+}
+impl RawKeys {
+    fn new() -> io::Result<Self> {
+        let mut saved = MaybeUninit::uninit();
+        // SAFETY: `tcgetattr` initializes the settings when it succeeds
+        let saved = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, saved.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            saved.assume_init()
+        };
+        let mut raw: libc::termios = saved;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_iflag &= !(libc::IXON | libc::ICRNL);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        set_termios(&raw)?;
+        Ok(Self {
+            saved,
+            pending: VecDeque::new(),
+        })
+    }
+    /// Waits for a key, up to `timeout` if given, returning `None` when it elapses
+    ///
+    /// Fails with [`io::ErrorKind::UnexpectedEof`] once stdin is closed.
+    fn read_key(&mut self, timeout: Option<Duration>) -> io::Result<Option<Key>> {
+        if self.pending.is_empty() {
+            let timeout = timeout.map_or(-1, |timeout| {
+                i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX)
+            });
+            let mut stdin = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: polls the single, valid `pollfd`
+            match unsafe { libc::poll(&mut stdin, 1, timeout) } {
+                0 => return Ok(None),
+                ready if ready < 0 => {
+                    let err = io::Error::last_os_error();
+                    return match err.kind() {
+                        io::ErrorKind::Interrupted => Ok(None),
+                        _ => Err(err),
+                    };
+                }
+                _ => {}
+            }
+            let mut buffer = [0_u8; 64];
+            // SAFETY: reads at most the length of the buffer into it
+            let count =
+                unsafe { libc::read(libc::STDIN_FILENO, buffer.as_mut_ptr().cast(), buffer.len()) };
+            match count {
+                0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+                count if count < 0 => return Err(io::Error::last_os_error()),
+                count => self.pending.extend(&buffer[..count as usize]),
+            }
+        }
+        Ok(decode_key(&mut self.pending))
+    }
+}
+impl Drop for RawKeys {
+    fn drop(&mut self) {
+        let _ = set_termios(&self.saved);
+    }
+}
+fn set_termios(settings: &libc::termios) -> io::Result<()> {
+    // SAFETY: the settings come from `tcgetattr`
+    if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, settings) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Takes the first key from raw terminal input, including arrow key escape sequences
+fn decode_key(bytes: &mut VecDeque<u8>) -> Option<Key> {
+    let first = bytes.pop_front()?;
+    let key = match first {
+        b'\x1b' => match (bytes.front(), bytes.get(1)) {
+            (Some(b'['), Some(&last @ (b'A' | b'B' | b'C' | b'D'))) => {
+                bytes.drain(..2);
+                match last {
+                    b'A' => Key::ArrowUp,
+                    b'B' => Key::ArrowDown,
+                    b'C' => Key::ArrowRight,
+                    _ => Key::ArrowLeft,
+                }
+            }
+            _ => Key::Escape,
+        },
+        b'\r' | b'\n' => Key::Enter,
+        0x7f => Key::Backspace,
+        byte if byte.is_ascii() => Key::Char(char::from(byte)),
+        _ => Key::Unknown,
+    };
+    Some(key)
+}
+
+/// Shows the cursor again when dropped, even on error
+struct HiddenCursor<'a>(&'a Term);
+impl<'a> HiddenCursor<'a> {
+    fn new(term: &'a Term) -> std::io::Result<Self> {
+        term.hide_cursor()?;
+        Ok(Self(term))
+    }
+}
+impl Drop for HiddenCursor<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    /// Move on to the next platform
+    Next,
+    Quit,
+}
+
+/// Playback state, keeping every step up to the first repeat for stepping backwards
+struct Animation {
+    /// Platform after each step, up to the furthest step computed or the end of the first cycle
+    history: Vec<Platform>,
+    /// First step for each platform and the position of its next tilt in the sequence
+    seen: HashMap<(Platform, usize), usize>,
+    cycle: Option<Cycle>,
+    step: usize,
+    playing: bool,
+    reverse: bool,
+    delay: Duration,
+    rules: Rules,
+}
+impl Animation {
+    fn new(platform: Platform, rules: Rules) -> Self {
+        let seen = HashMap::from([((platform.clone(), 0), 0)]);
+        Self {
+            history: vec![platform],
+            seen,
+            cycle: None,
+            step: 0,
+            playing: true,
+            reverse: false,
+            delay: DEFAULT_DELAY,
+            rules,
+        }
+    }
+    fn platform(&self) -> &Platform {
+        let index = match self.cycle {
+            // steps past the history repeat the cycle
+            Some(Cycle { start, length }) if self.step >= start => {
+                start + (self.step - start) % length
+            }
+            _ => self.step,
+        };
+        &self.history[index]
+    }
+    fn direction_at(&self, step: usize) -> Direction {
+        self.rules.sequence.direction_at(step)
+    }
+    fn step_forward(&mut self) {
+        let next_step = self.step + 1;
+        // once found, the cycle repeats forever, so stop storing states
+        if self.cycle.is_none() && next_step == self.history.len() {
+            let mut next = self.platform().clone();
+            next.roll_stones(self.direction_at(self.step));
+            let key = (next.clone(), next_step % self.rules.sequence.len());
+            if let Some(&start) = self.seen.get(&key) {
+                self.cycle = Some(Cycle {
+                    start,
+                    length: next_step - start,
+                });
+                self.seen.clear();
+            } else {
+                self.seen.insert(key, next_step);
+                self.history.push(next);
+            }
+        }
+        self.step = next_step;
+    }
+    fn step_back(&mut self) {
+        self.step = self.step.saturating_sub(1);
+    }
+    /// Plays one step, pausing at the start when reversed
+    fn tick(&mut self) {
+        if !self.reverse {
+            self.step_forward();
+        } else if self.step == 0 {
+            self.playing = false;
+        } else {
+            self.step_back();
+        }
+    }
+    fn handle(&mut self, key: Key) -> Flow {
+        match key {
+            Key::Char(' ') => self.playing = !self.playing,
+            Key::Char('n') | Key::ArrowRight => {
+                self.playing = false;
+                self.step_forward();
+            }
+            Key::Char('p') | Key::ArrowLeft => {
+                self.playing = false;
+                self.step_back();
+            }
+            Key::Char('r') => self.reverse = !self.reverse,
+            Key::Char('+') => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Key::Char('-') => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Key::Char('q') | Key::Enter => return Flow::Next,
+            Key::Escape | Key::Char('\u{3}') => return Flow::Quit,
+            _ => {}
+        }
+        Flow::Continue
+    }
+    /// Returns the styled lines of the platform and the readout below it
+    fn frame(&self, title: &str) -> Vec<String> {
+        let round = Style::new().yellow().bold();
+        let cube = Style::new().dim();
+
+        let Self {
+            step,
+            playing,
+            reverse,
+            delay,
+            ..
+        } = *self;
+        let platform = self.platform();
+
+        let mut lines = vec![title.to_owned()];
+        lines.extend(platform.grid.rows().map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Cell::Some(Rock::Round) => round.apply_to('O').to_string(),
+                    Cell::Some(Rock::Cube) => cube.apply_to('#').to_string(),
+                    Cell::None => ".".to_owned(),
+                })
+                .collect::<String>()
+        }));
+
+        let last_tilt = step.checked_sub(1).map_or("none".to_owned(), |last| {
+            format!("{:?}", self.direction_at(last))
+        });
+        let spin_length = self.rules.sequence.len();
+        lines.push(format!(
+            "Step {step} (spin cycle {}), last tilt {last_tilt}",
+            step / spin_length
+        ));
+        let edge = self.rules.edge;
+        lines.push(format!(
+            "Load on the {} edge: {}",
+            format!("{edge:?}").to_lowercase(),
+            platform.get_load(edge)
+        ));
+        lines.push(match self.cycle {
+            Some(Cycle { start, length }) => format!(
+                "Cycle: {length} steps ({} spin cycles), from step {start}",
+                length / spin_length
+            ),
+            None => "Cycle: not detected yet".to_owned(),
+        });
+        let playback = match (playing, reverse) {
+            (true, false) => "playing",
+            (true, true) => "playing in reverse",
+            (false, false) => "paused",
+            (false, true) => "paused (reversed)",
+        };
+        lines.push(format!("{playback}, {}ms per step", delay.as_millis()));
+        lines.push(
+            Style::new()
+                .dim()
+                .apply_to(
+                    "[space] pause  [n/→] step  [p/←] back  [r] reverse  [+/-] speed  [q] next",
+                )
+                .to_string(),
+        );
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_key, Animation, Flow};
+    use crate::{
+        cycle,
+        days::day14_rocks_roll::{Direction, Platform, Rules},
+        RuleArgs,
+    };
+    use console::Key;
+
+    const SAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    fn sample() -> Platform {
+        Platform::new_from_str(SAMPLE).unwrap().unwrap()
+    }
+
+    #[test]
+    fn decodes_keys() {
+        let mut bytes = b"n\x1b[Cq\x03\r\x1b".iter().copied().collect();
+        let keys: Vec<_> = std::iter::from_fn(|| decode_key(&mut bytes)).collect();
+        assert_eq!(
+            keys,
+            [
+                Key::Char('n'),
+                Key::ArrowRight,
+                Key::Char('q'),
+                Key::Char('\u{3}'),
+                Key::Enter,
+                Key::Escape
+            ]
+        );
+    }
+
+    #[test]
+    fn steps_forward_and_back() {
+        let mut animation = Animation::new(sample(), Rules::default());
+        assert_eq!(animation.handle(Key::Char('n')), Flow::Continue);
+        assert!(!animation.playing);
+
+        let mut north = sample();
+        north.roll_stones(Direction::North);
+        assert_eq!(animation.platform(), &north);
+
+        animation.handle(Key::ArrowLeft);
+        assert_eq!(animation.platform(), &sample());
+        animation.handle(Key::ArrowLeft);
+        assert_eq!(animation.step, 0);
+
+        // stepping forward again reuses the history
+        animation.handle(Key::ArrowRight);
+        assert_eq!(animation.history.len(), 2);
+        assert_eq!(animation.platform(), &north);
+    }
+
+    #[test]
+    fn reverse_pauses_at_start() {
+        let mut animation = Animation::new(sample(), Rules::default());
+        animation.tick();
+        animation.tick();
+        animation.handle(Key::Char('r'));
+        animation.tick();
+        animation.tick();
+        assert_eq!(animation.step, 0);
+        assert!(animation.playing);
+        animation.tick();
+        assert!(!animation.playing);
+        assert_eq!(animation.handle(Key::Char('q')), Flow::Next);
+    }
+
+    #[test]
+    fn detects_cycle() {
+        let mut animation = Animation::new(sample(), Rules::default());
+        while animation.cycle.is_none() {
+            animation.tick();
+        }
+        let (expected, _) = cycle::find_cycle((sample(), 0), |(p, index)| {
+            let mut platform = p.clone();
+            platform.roll_stones(Rules::default().sequence.direction_at(*index));
+            (platform, (index + 1) % 4)
+        });
+        assert_eq!(animation.cycle, Some(expected));
+        assert_eq!(expected.length, 7 * 4);
+
+        // playing on reuses the cycle, without growing the history
+        let history_len = animation.history.len();
+        assert_eq!(history_len, expected.start + expected.length);
+        let cycle_start = animation.history[expected.start].clone();
+        for _ in 0..(3 * expected.length) {
+            animation.tick();
+        }
+        assert_eq!(animation.history.len(), history_len);
+        let laps = (animation.step - expected.start) / expected.length;
+        animation.step = expected.start + laps * expected.length;
+        assert_eq!(animation.platform(), &cycle_start);
+        animation.step_back();
+        assert_eq!(
+            animation.platform(),
+            &animation.history[expected.start + expected.length - 1]
+        );
+
+        let readout: Vec<_> = animation
+            .frame("Sample")
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect();
+        assert_eq!(readout[0], "Sample");
+        assert!(readout.contains(&format!(
+            "Cycle: 28 steps (7 spin cycles), from step {}",
+            expected.start
+        )));
+    }
+
+    #[test]
+    fn frame_shows_load() {
+        let mut animation = Animation::new(sample(), Rules::default());
+        animation.tick();
+        let readout: Vec<_> = animation
+            .frame("Sample")
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect();
+        assert_eq!(readout[1], "OOOO.#.O..");
+        assert!(readout.contains(&"Step 1 (spin cycle 0), last tilt North".to_owned()));
+        assert!(readout.contains(&"Load on the north edge: 136".to_owned()));
+        assert!(readout.contains(&"Cycle: not detected yet".to_owned()));
+    }
+
+    #[test]
+    fn frame_follows_rules() {
+        let rules = Rules::new(&RuleArgs {
+            sequence: Some("NNE".to_owned()),
+            edge: Some("e".to_owned()),
+        })
+        .unwrap();
+        let mut animation = Animation::new(sample(), rules);
+        for _ in 0..3 {
+            animation.tick();
+        }
+        let mut east = sample();
+        east.roll_stones(Direction::North);
+        east.roll_stones(Direction::East);
+        assert_eq!(animation.platform(), &east);
+
+        let readout: Vec<_> = animation
+            .frame("Sample")
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect();
+        assert!(readout.contains(&"Step 3 (spin cycle 1), last tilt East".to_owned()));
+        assert!(readout.contains(&format!(
+            "Load on the east edge: {}",
+            east.get_load(Direction::East)
+        )));
+    }
+}
@@ -0,0 +1,427 @@
+//! Bit-packed platform, tilting whole words of round rocks at a time
+//!
+//! Round rocks are one bitset per line, where lines are either the rows (for east/west tilts) or
+//! the columns (for north/south tilts), transposing between the two when the tilt axis changes.
+//! Each bitset has just enough words for its line, so memory grows with the area of the platform.
+//! Cube rocks never move, so only the free spans between them are kept, shared by all clones.
+//! Clones, comparisons and hashes for cycle detection only touch the round rock words.
+
+use super::{Cell, Direction, Platform, Rock, TiltSequence};
+use crate::{cycle, point::Point};
+use std::{ops::Range, rc::Rc};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Which lines the round rock bitsets hold
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Lines {
+    Rows,
+    Cols,
+}
+
+/// Unchanging shape of the platform
+#[derive(Debug, PartialEq, Eq)]
+struct Layout {
+    width: usize,
+    height: usize,
+    /// Spans of each row free of cube rocks, as column ranges
+    row_spans: Vec<Vec<Range<usize>>>,
+    /// Spans of each column free of cube rocks, as row ranges
+    col_spans: Vec<Vec<Range<usize>>>,
+}
+impl Layout {
+    fn spans(&self, lines: Lines) -> &[Vec<Range<usize>>] {
+        match lines {
+            Lines::Rows => &self.row_spans,
+            Lines::Cols => &self.col_spans,
+        }
+    }
+    /// Number of lines and bits per line
+    fn shape(&self, lines: Lines) -> (usize, usize) {
+        match lines {
+            Lines::Rows => (self.height, self.width),
+            Lines::Cols => (self.width, self.height),
+        }
+    }
+    /// Words per line
+    fn stride(&self, lines: Lines) -> usize {
+        words(self.shape(lines).1)
+    }
+}
+/// Words holding the bits of one line
+fn words(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS).max(1)
+}
+
+#[derive(Clone, Debug)]
+pub struct PackedPlatform {
+    layout: Rc<Layout>,
+    lines: Lines,
+    /// Round rocks, with the layout's stride for the lines
+    round: Vec<u64>,
+}
+impl From<&Platform> for PackedPlatform {
+    fn from(platform: &Platform) -> Self {
+        let grid = &platform.grid;
+        let (width, height) = (grid.width(), grid.height());
+        let stride = words(width);
+
+        let mut round = vec![0; height * stride];
+        for (Point { row, col }, &cell) in grid.iter() {
+            if cell == Cell::Some(Rock::Round) {
+                round[row * stride + col / WORD_BITS] |= 1 << (col % WORD_BITS);
+            }
+        }
+
+        let is_cube = |row, col| grid[Point { row, col }] == Cell::Some(Rock::Cube);
+        let row_spans = (0..height)
+            .map(|row| free_spans(width, |col| is_cube(row, col)))
+            .collect();
+        let col_spans = (0..width)
+            .map(|col| free_spans(height, |row| is_cube(row, col)))
+            .collect();
+
+        Self {
+            layout: Rc::new(Layout {
+                width,
+                height,
+                row_spans,
+                col_spans,
+            }),
+            lines: Lines::Rows,
+            round,
+        }
+    }
+}
+/// Returns the nonempty ranges between the blocked indices
+fn free_spans(len: usize, is_blocked: impl Fn(usize) -> bool) -> Vec<Range<usize>> {
+    let mut spans = vec![];
+    let mut start = 0;
+    for index in 0..=len {
+        if index == len || is_blocked(index) {
+            if start < index {
+                spans.push(start..index);
+            }
+            start = index + 1;
+        }
+    }
+    spans
+}
+impl PackedPlatform {
+    pub(super) fn roll_stones(&mut self, direction: Direction) {
+        let (lines, toward_start) = match direction {
+            Direction::North => (Lines::Cols, true),
+            Direction::South => (Lines::Cols, false),
+            Direction::West => (Lines::Rows, true),
+            Direction::East => (Lines::Rows, false),
+        };
+        if self.lines != lines {
+            let (lines_count, len) = self.layout.shape(self.lines);
+            self.round = transpose(&self.round, lines_count, len);
+            self.lines = lines;
+        }
+        let stride = self.layout.stride(lines);
+        for (line_spans, line) in self
+            .layout
+            .spans(lines)
+            .iter()
+            .zip(self.round.chunks_exact_mut(stride))
+        {
+            for span in line_spans {
+                let count: usize = word_masks(span.clone())
+                    .map(|(word, mask)| (line[word] & mask).count_ones() as usize)
+                    .sum();
+                for (word, mask) in word_masks(span.clone()) {
+                    line[word] &= !mask;
+                }
+                let packed = if toward_start {
+                    span.start..(span.start + count)
+                } else {
+                    (span.end - count)..span.end
+                };
+                for (word, mask) in word_masks(packed) {
+                    line[word] |= mask;
+                }
+            }
+        }
+    }
+    pub(super) fn get_load(&self, edge: Direction) -> usize {
+        let Layout { width, height, .. } = *self.layout;
+        let lines = self.round.chunks_exact(self.layout.stride(self.lines));
+        match (self.lines, edge) {
+            // whole rows share one weight
+            (Lines::Rows, Direction::North | Direction::South) => lines
+                .enumerate()
+                .map(|(row, line)| {
+                    let count: u32 = line.iter().map(|word| word.count_ones()).sum();
+                    count as usize * edge.load_weight(Point { row, col: 0 }, (width, height))
+                })
+                .sum(),
+            // whole columns share one weight
+            (Lines::Cols, Direction::West | Direction::East) => lines
+                .enumerate()
+                .map(|(col, line)| {
+                    let count: u32 = line.iter().map(|word| word.count_ones()).sum();
+                    count as usize * edge.load_weight(Point { row: 0, col }, (width, height))
+                })
+                .sum(),
+            (lines_kind, _) => lines
+                .enumerate()
+                .flat_map(|(line, words)| words.iter().enumerate().map(move |w| (line, w)))
+                .map(|(line, (word_index, &word))| {
+                    let mut word = word;
+                    let mut sum = 0;
+                    while word != 0 {
+                        let index = word_index * WORD_BITS + word.trailing_zeros() as usize;
+                        let point = match lines_kind {
+                            Lines::Rows => Point {
+                                row: line,
+                                col: index,
+                            },
+                            Lines::Cols => Point {
+                                row: index,
+                                col: line,
+                            },
+                        };
+                        sum += edge.load_weight(point, (width, height));
+                        word &= word - 1;
+                    }
+                    sum
+                })
+                .sum(),
+        }
+    }
+    pub(super) fn perform_cycles(self, sequence: &TiltSequence, total_cycles_count: usize) -> Self {
+        // NOTE: A cycle is one tilt for each direction in the sequence
+        let steps_count = sequence.len() * total_cycles_count;
+        let (platform, _) = cycle::nth_state((self, 0), steps_count, |(platform, index)| {
+            let mut platform = platform.clone();
+            platform.roll_stones(sequence.direction_at(*index));
+            (platform, (index + 1) % sequence.len())
+        });
+        platform
+    }
+    #[cfg(test)]
+    pub(super) fn to_platform(&self) -> Platform {
+        let Layout {
+            width,
+            height,
+            ref row_spans,
+            ..
+        } = *self.layout;
+        let stride = self.layout.stride(self.lines);
+        let grid = crate::grid::Grid::from_fn(width, height, |point| {
+            let (line, index) = match self.lines {
+                Lines::Rows => (point.row, point.col),
+                Lines::Cols => (point.col, point.row),
+            };
+            let word = self.round[line * stride + index / WORD_BITS];
+            if !row_spans[point.row]
+                .iter()
+                .any(|span| span.contains(&point.col))
+            {
+                Cell::Some(Rock::Cube)
+            } else if word & (1 << (index % WORD_BITS)) != 0 {
+                Cell::Some(Rock::Round)
+            } else {
+                Cell::None
+            }
+        });
+        Platform { grid }
+    }
+}
+impl PartialEq for PackedPlatform {
+    fn eq(&self, other: &Self) -> bool {
+        let same_layout = Rc::ptr_eq(&self.layout, &other.layout) || self.layout == other.layout;
+        same_layout && self.lines == other.lines && self.round == other.round
+    }
+}
+impl Eq for PackedPlatform {}
+impl std::hash::Hash for PackedPlatform {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // NOTE: the layout is the same for all states of one platform
+        self.lines.hash(state);
+        self.round.hash(state);
+    }
+}
+
+/// Returns the word index and bit mask for each word overlapping the range of bits
+fn word_masks(range: Range<usize>) -> impl Iterator<Item = (usize, u64)> {
+    let Range { start, end } = range;
+    let words = if start < end {
+        (start / WORD_BITS)..end.div_ceil(WORD_BITS)
+    } else {
+        0..0
+    };
+    words.map(move |word| {
+        let word_start = word * WORD_BITS;
+        let low = start.max(word_start) - word_start;
+        let high = end.min(word_start + WORD_BITS) - word_start;
+        let ones = u64::MAX >> (WORD_BITS - (high - low));
+        (word, ones << low)
+    })
+}
+
+/// Transposes a bit matrix of `lines` lines of `len` bits, into `len` lines of `lines` bits
+///
+/// Each 64x64 block is transposed into its mirrored position across the diagonal.
+fn transpose(matrix: &[u64], lines: usize, len: usize) -> Vec<u64> {
+    let (stride, transposed_stride) = (words(len), words(lines));
+    let mut transposed = vec![0; len * transposed_stride];
+    for block_row in 0..transposed_stride {
+        for block_col in 0..stride {
+            let mut block = [0; WORD_BITS];
+            for (i, word) in block.iter_mut().enumerate() {
+                let line = block_row * WORD_BITS + i;
+                if line < lines {
+                    *word = matrix[line * stride + block_col];
+                }
+            }
+            transpose_block(&mut block);
+            for (i, word) in block.into_iter().enumerate() {
+                let line = block_col * WORD_BITS + i;
+                if line < len {
+                    transposed[line * transposed_stride + block_row] = word;
+                }
+            }
+        }
+    }
+    transposed
+}
+
+/// Transposes a 64x64 bit matrix, where bit `c` of word `r` is the cell at row `r`, column `c`
+///
+/// Swaps the off-diagonal quadrants, then recurses into each quadrant (Hacker's Delight, 7-3).
+fn transpose_block(block: &mut [u64; WORD_BITS]) {
+    let mut width = WORD_BITS / 2;
+    let mut mask: u64 = u64::MAX >> width;
+    while width != 0 {
+        let mut k = 0;
+        while k < WORD_BITS {
+            let swap = ((block[k] >> width) ^ block[k + width]) & mask;
+            block[k] ^= swap << width;
+            block[k + width] ^= swap;
+            k = (k + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{transpose, word_masks, PackedPlatform, WORD_BITS};
+    use crate::{
+        days::day14_rocks_roll::{parse_grids, Direction, Rules, TiltSequence},
+        generate,
+    };
+
+    #[test]
+    fn masks_span_words() {
+        let masks: Vec<_> = word_masks(60..130).collect();
+        assert_eq!(
+            masks,
+            vec![(0, 0xf << 60), (1, u64::MAX), (2, 0b11)],
+            "{masks:x?}"
+        );
+        assert_eq!(word_masks(3..3).count(), 0);
+        assert_eq!(word_masks(0..64).collect::<Vec<_>>(), vec![(0, u64::MAX)]);
+    }
+
+    #[test]
+    fn transposes_matrix() {
+        let is_set = |row: usize, col: usize| (row * 7 + col * col * 3).is_multiple_of(5);
+        for (height, width) in [(128_usize, 128_usize), (3, 200), (70, 5), (1, 1)] {
+            let stride = width.div_ceil(WORD_BITS);
+            let mut matrix = vec![0; height * stride];
+            for row in 0..height {
+                for col in 0..width {
+                    if is_set(row, col) {
+                        matrix[row * stride + col / WORD_BITS] |= 1 << (col % WORD_BITS);
+                    }
+                }
+            }
+            let transposed = transpose(&matrix, height, width);
+            let transposed_stride = height.div_ceil(WORD_BITS);
+            assert_eq!(transposed.len(), width * transposed_stride);
+            for row in 0..width {
+                for col in 0..height {
+                    let word = transposed[row * transposed_stride + col / WORD_BITS];
+                    let bit = word >> (col % WORD_BITS) & 1;
+                    assert_eq!(
+                        bit == 1,
+                        is_set(col, row),
+                        "{height}x{width} ({row}, {col})"
+                    );
+                }
+            }
+            assert_eq!(transpose(&transposed, width, height), matrix);
+        }
+    }
+
+    #[test]
+    fn matches_grid_platform() {
+        // sizes on both sides of the word boundary
+        let mut inputs: Vec<_> = [(1, 0), (3, 1), (10, 2), (63, 3), (64, 4), (65, 5), (130, 6)]
+            .into_iter()
+            .map(|(size, seed)| (format!("{size}"), generate::generate(14, size, seed)))
+            .collect();
+        // and wide or tall, with more words per line one way than the other
+        let wide = generate::generate(14, 130, 7).unwrap();
+        let wide = wide.lines().take(3).collect::<Vec<_>>().join("\n");
+        let tall = generate::generate(14, 130, 8).unwrap();
+        let tall = tall.lines().map(|line| &line[..2]).collect::<Vec<_>>();
+        inputs.push(("3x130".to_owned(), Ok(wide)));
+        inputs.push(("130x2".to_owned(), Ok(tall.join("\n"))));
+        for (name, input) in inputs {
+            let grids = parse_grids(&input.unwrap()).unwrap();
+            let mut grid = grids[0].clone();
+            let mut packed = PackedPlatform::from(&grid);
+            assert_eq!(packed.to_platform(), grid, "{name}");
+
+            let sequence = TiltSequence::default();
+            for step in 0..10 {
+                let direction = sequence.direction_at(step);
+                grid.roll_stones(direction);
+                packed.roll_stones(direction);
+                assert_eq!(packed.to_platform(), grid, "{name} step {step}");
+                for edge in [
+                    Direction::North,
+                    Direction::West,
+                    Direction::South,
+                    Direction::East,
+                ] {
+                    assert_eq!(
+                        packed.get_load(edge),
+                        grid.get_load(edge),
+                        "{name} step {step} {edge:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cycles_match_grid_platform() {
+        for (seed, sequence) in (0..).zip(["NWSE", "NNES", "E", "SWNEW"]) {
+            let sequence: TiltSequence = sequence.parse().unwrap();
+            let rules = Rules {
+                sequence: sequence.clone(),
+                edge: Direction::North,
+            };
+            let input = generate::generate(14, 12, seed).unwrap();
+            let grid = parse_grids(&input).unwrap().remove(0);
+            let packed = PackedPlatform::from(&grid);
+            for cycles in [1, 7, 1_000_000_000] {
+                assert_eq!(
+                    packed
+                        .clone()
+                        .perform_cycles(&sequence, cycles)
+                        .to_platform(),
+                    grid.clone().perform_cycles(&rules, cycles),
+                    "seed {seed}, {sequence:?}, {cycles} cycles"
+                );
+            }
+        }
+    }
+}
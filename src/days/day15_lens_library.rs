@@ -1,36 +1,35 @@
-use anyhow::Context;
+use crate::{log_trace, ParseError};
 
-fn main() -> anyhow::Result<()> {
-    eprintln!("hello, lava manual!");
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 15;
+    type Parsed = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    let input = advent_2023::get_input_string()?;
-    let Stats {
-        sum_of_hashes,
-        focusing_power,
-    } = analyze(&input)?;
-
-    eprintln!("Sum of hashes: {sum_of_hashes}");
-    eprintln!("Boxes total focusing power: {focusing_power}");
-
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_line(input)?.to_owned())
+    }
+    fn part1(line: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(sum_of_hashes(line))
+    }
+    fn part2(line: &Self::Parsed) -> anyhow::Result<u32> {
+        let boxes = Boxes::try_from(line.as_str())?;
+        Ok(boxes.get_focus_power())
+    }
 }
 
+#[cfg(test)]
 struct Stats {
     sum_of_hashes: u32,
     focusing_power: u32,
 }
 
+#[cfg(test)]
 fn analyze(input: &str) -> anyhow::Result<Stats> {
-    let mut lines = input.lines();
+    let line = parse_line(input)?;
 
-    let Some(line) = lines.next() else {
-        anyhow::bail!("no lines in the input")
-    };
-    if let Some(extra) = lines.next() {
-        anyhow::bail!("extra line {extra:?}")
-    }
-
-    let sum_of_hashes = line.split(',').map(ascii_hash).map(u32::from).sum();
+    let sum_of_hashes = sum_of_hashes(line);
 
     let boxes = Boxes::try_from(line)?;
     let focusing_power = boxes.get_focus_power();
@@ -41,6 +40,30 @@ fn analyze(input: &str) -> anyhow::Result<Stats> {
     })
 }
 
+/// Returns the single line of steps, checking each is a valid ASCII command
+fn parse_line(input: &str) -> Result<&str, ParseError> {
+    let mut lines = input.lines();
+
+    let Some(line) = lines.next() else {
+        return Err(ParseError::new("no lines in the input"));
+    };
+    if let Some(extra) = lines.next() {
+        return Err(ParseError::new("extra line").at(1, extra, extra));
+    }
+    if let Some((index, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+        let text = &line[index..(index + c.len_utf8())];
+        return Err(ParseError::unexpected_char(c).at(0, line, text));
+    }
+    for step in line.split(',') {
+        Command::try_from(step).map_err(|err| err.at(0, line, step))?;
+    }
+    Ok(line)
+}
+
+fn sum_of_hashes(line: &str) -> u32 {
+    line.split(',').map(ascii_hash).map(u32::from).sum()
+}
+
 fn ascii_hash(input: &str) -> u8 {
     let result = input.chars().fold(0, |accum, c| {
         assert!(c.is_ascii());
@@ -48,7 +71,7 @@ fn ascii_hash(input: &str) -> u8 {
         let multiplied = sum * 17;
         multiplied % (u32::from(u8::MAX) + 1)
     });
-    // println!("{input:?} -> {result}");
+    // eprintln!("{input:?} -> {result}");
     u8::try_from(result).expect("mod u8::MAX fits in u8")
 }
 
@@ -87,16 +110,16 @@ impl<'a> Boxes<'a> {
     }
 }
 impl<'a, 'b: 'a> TryFrom<&'b str> for Boxes<'a> {
-    type Error = anyhow::Error;
+    type Error = ParseError;
     fn try_from(value: &'b str) -> Result<Self, Self::Error> {
         let mut boxes = Boxes::new();
         for command_str in value.split(',') {
-            let command = Command::try_from(command_str).with_context(|| command_str.to_owned())?;
+            let command =
+                Command::try_from(command_str).map_err(|err| err.at(0, value, command_str))?;
             let which = ascii_hash(command.label());
             boxes.update(which, command);
 
-            println!("After {command_str:?}:");
-            println!("{boxes}");
+            log_trace!("After {command_str:?}:\n{boxes}");
         }
         Ok(boxes)
     }
@@ -181,20 +204,23 @@ impl<'a> Command<'a> {
     }
 }
 impl<'a, 'b: 'a> TryFrom<&'b str> for Command<'a> {
-    type Error = anyhow::Error;
+    type Error = ParseError;
     fn try_from(value: &'b str) -> Result<Self, Self::Error> {
         const EQUALS: &str = "=";
         const DASH: &str = "-";
 
         let command = if let Some((label, lens_str)) = value.split_once(EQUALS) {
             let Ok(lens) = lens_str.parse() else {
-                anyhow::bail!("invalid lens number {lens_str:?}")
+                return Err(ParseError::new(format!("invalid lens number {lens_str:?}"))
+                    .with_span((label.len() + EQUALS.len())..value.len()));
             };
             Self::Insert(LabelLens { label, lens })
         } else if let Some(label) = value.strip_suffix(DASH) {
             Self::Remove { label }
         } else {
-            anyhow::bail!("entry does not contain {EQUALS:?} or {DASH:?}")
+            return Err(ParseError::new(format!(
+                "entry does not contain {EQUALS:?} or {DASH:?}"
+            )));
         };
         Ok(command)
     }
@@ -202,7 +228,9 @@ impl<'a, 'b: 'a> TryFrom<&'b str> for Command<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{analyze, ascii_hash, Boxes, Command, LabelLens};
+    use crate::days::day15_lens_library::{
+        analyze, ascii_hash, parse_line, Boxes, Command, LabelLens,
+    };
 
     #[test]
     fn sample_hash() {
@@ -261,4 +289,20 @@ mod tests {
         let box_cde = b.get_mut(ascii_hash("cde"));
         assert_eq!(box_cde.get_mut("cde"), None);
     }
+
+    #[test]
+    fn parse_error_location() {
+        let err = parse_line("rn=1,cm-,qp=x,cm=2\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 13: invalid lens number \"x\""
+        );
+        let err = parse_line("rn=1,cm,qp=3").unwrap_err();
+        assert_eq!(err.location().expect("located").text, "cm");
+        let err = parse_line("rn=1,cé-").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: unexpected character 'é'"
+        );
+    }
 }
@@ -0,0 +1,232 @@
+use crate::{
+    dimension::Dimension,
+    direction::{Direction, Mirror, EAST, NORTH, SOUTH, WEST},
+    grid::Grid,
+    log_debug,
+    point::Point,
+    search, ParseError,
+};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 16;
+    type Parsed = Grid<Cell>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let Some(grid) = Grid::parse(input, Cell::try_from)? else {
+            anyhow::bail!("no grid in input")
+        };
+        log_debug!("{grid:?}");
+        Ok(grid)
+    }
+    fn part1(grid: &Grid<Cell>) -> anyhow::Result<usize> {
+        Ok(default_energy_sum(grid))
+    }
+    fn part2(grid: &Grid<Cell>) -> anyhow::Result<usize> {
+        Ok(highest_energy_sum(grid))
+    }
+}
+
+#[cfg(test)]
+struct Stats {
+    default_energy_sum: usize,
+    highest_energy_sum: usize,
+}
+
+#[cfg(test)]
+fn eval_input(input: &str) -> anyhow::Result<Stats> {
+    let Some(grid) = Grid::parse(input, Cell::try_from)? else {
+        anyhow::bail!("no grid in input")
+    };
+    Ok(Stats {
+        default_energy_sum: default_energy_sum(&grid),
+        highest_energy_sum: highest_energy_sum(&grid),
+    })
+}
+
+fn default_energy_sum(grid: &Grid<Cell>) -> usize {
+    const DEFAULT_ENTRY: (Point, Direction) = (Point { row: 0, col: 0 }, EAST);
+
+    let default_energy = EnergizedGrid::new(grid, DEFAULT_ENTRY);
+    log_debug!("{default_energy:?}");
+    default_energy.sum()
+}
+
+fn highest_energy_sum(grid: &Grid<Cell>) -> usize {
+    let row_first = 0;
+    let row_last = grid.height() - 1;
+    let col_first = 0;
+    let col_last = grid.width() - 1;
+
+    let row_entries = (row_first..=row_last).flat_map(|row| {
+        vec![
+            (
+                Point {
+                    row,
+                    col: col_first,
+                },
+                EAST,
+            ),
+            (
+                Point {
+                    row,
+                    col: col_last, //
+                },
+                WEST,
+            ),
+        ]
+    });
+    let col_entries = (col_first..=col_last).flat_map(|col| {
+        vec![
+            (
+                Point {
+                    row: row_first,
+                    col,
+                },
+                SOUTH,
+            ),
+            (
+                Point {
+                    row: row_last, //
+                    col,
+                },
+                NORTH,
+            ),
+        ]
+    });
+
+    row_entries
+        .chain(col_entries)
+        .map(|entry| EnergizedGrid::new(grid, entry).sum())
+        .max()
+        .expect("nonempty entrypoints")
+}
+
+struct EnergizedGrid<'a> {
+    cells: &'a Grid<Cell>,
+    energized: Grid<Energized>,
+}
+impl<'a> EnergizedGrid<'a> {
+    fn new(cells: &'a Grid<Cell>, entry: (Point, Direction)) -> Self {
+        let beams = search::bfs(entry, |&(point, direction)| {
+            next_beams(cells, point, direction)
+        });
+        let mut energized = cells.empty_with(Energized::default);
+        for &(point, _) in beams.nodes() {
+            energized[point] = Energized::True;
+        }
+        Self { cells, energized }
+    }
+    fn sum(&self) -> usize {
+        self.energized
+            .cells()
+            .iter()
+            .map(|&elem| match elem {
+                Energized::True => 1,
+                Energized::False => 0,
+            })
+            .sum()
+    }
+}
+
+/// Returns the beams leaving the cell at `point`, for a beam entering it in the direction
+fn next_beams(
+    cells: &Grid<Cell>,
+    point: Point,
+    direction: Direction,
+) -> impl Iterator<Item = (Point, Direction)> + '_ {
+    let (next1, next2) = match cells[point] {
+        Cell::Empty => (direction, None),
+        Cell::Mirror(mirror) => (direction.reflect(mirror), None),
+        Cell::SplitAlongThis(Dimension::Row) => (WEST, Some(EAST)),
+        Cell::SplitAlongThis(Dimension::Col) => (NORTH, Some(SOUTH)),
+    };
+    std::iter::once(next1)
+        .chain(next2)
+        .filter_map(move |next_direction| {
+            let (dest, _) = cells.neighbor(point, next_direction)?;
+            Some((dest, next_direction))
+        })
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Energized {
+    True,
+    #[default]
+    False,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Mirror(Mirror),
+    SplitAlongThis(Dimension),
+}
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '\\' => Ok(Self::Mirror(Mirror::NwToSe)),
+            '/' => Ok(Self::Mirror(Mirror::SwToNe)),
+            '-' => Ok(Self::SplitAlongThis(Dimension::Row)),
+            '|' => Ok(Self::SplitAlongThis(Dimension::Col)),
+            extra => Err(ParseError::unexpected_char(extra)),
+        }
+    }
+}
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::Mirror(Mirror::NwToSe) => '\\',
+            Cell::Mirror(Mirror::SwToNe) => '/',
+            Cell::SplitAlongThis(Dimension::Row) => '-',
+            Cell::SplitAlongThis(Dimension::Col) => '|',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl std::fmt::Debug for Energized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Energized::True => '#',
+            Energized::False => '.',
+        };
+        write!(f, "{c}")
+    }
+}
+impl std::fmt::Debug for EnergizedGrid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { cells, energized } = self;
+        writeln!(f, "Grid:")?;
+        writeln!(f, "{cells:?}")?;
+        writeln!(f, "Energized?")?;
+        writeln!(f, "{energized:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day16_mirror_beams::eval_input;
+
+    #[test]
+    fn sample_input() {
+        let input = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."#;
+        let stats = eval_input(input).unwrap();
+        assert_eq!(stats.default_energy_sum, 46);
+        assert_eq!(stats.highest_energy_sum, 51);
+    }
+}
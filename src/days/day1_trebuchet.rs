@@ -1,32 +1,56 @@
-use advent_2023::CharScanner;
-use clap::Parser;
+use crate::{CharScanner, ParseError};
 use std::collections::BTreeMap;
-use std::path::PathBuf;
 
-#[derive(Parser, Debug)]
-struct Args {
-    filename: Option<PathBuf>,
-}
-
-fn main() -> anyhow::Result<()> {
-    println!("hello, trebuchet!");
-    let input = advent_2023::get_input_string()?;
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 1;
+    type Parsed = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    let sum = sum_calibration_amended_by_a_very_young_elf(&input);
-    println!("Sum is: {sum}");
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        check_calibration_chars(input)?;
+        Ok(input.to_owned())
+    }
+    fn part1(input: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(sum_calibration(input, Calibration::DigitsOnly))
+    }
+    fn part2(input: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(sum_calibration_amended_by_a_very_young_elf(input))
+    }
+}
 
+/// Checks the calibration document only holds letters and digits
+fn check_calibration_chars(input: &str) -> Result<(), ParseError> {
+    for (line_index, line) in input.lines().enumerate() {
+        if let Some((index, c)) = line
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_alphanumeric())
+        {
+            let text = &line[index..(index + c.len_utf8())];
+            return Err(ParseError::unexpected_char(c).at(line_index, line, text));
+        }
+    }
     Ok(())
 }
 
+#[derive(Clone, Copy, Debug)]
+enum Calibration {
+    /// Only the numeric digits count
+    DigitsOnly,
+    /// Spelled-out number words also count as digits
+    NumberWords,
+}
+
 struct NumberScanner<'a> {
     scanner: CharScanner<'a, u32>,
-    number_words: BTreeMap<&'static str, usize>,
+    number_words: Option<BTreeMap<&'static str, usize>>,
 }
 const LOOKBACK_RANGE: (usize, usize) = (3, 5);
 const BASE_10: u32 = 10;
 
 impl<'a> NumberScanner<'a> {
-    fn new(line: &'a str) -> Self {
+    fn new(line: &'a str, calibration: Calibration) -> Self {
         let number_words = BTreeMap::from_iter([
             ("zero", 0),
             ("one", 1),
@@ -39,9 +63,15 @@ impl<'a> NumberScanner<'a> {
             ("eight", 8),
             ("nine", 9),
         ]);
-        Self {
-            scanner: CharScanner::new(line, Some(LOOKBACK_RANGE)),
-            number_words,
+        match calibration {
+            Calibration::DigitsOnly => Self {
+                scanner: CharScanner::new(line, None),
+                number_words: None,
+            },
+            Calibration::NumberWords => Self {
+                scanner: CharScanner::new(line, Some(LOOKBACK_RANGE)),
+                number_words: Some(number_words),
+            },
         }
     }
 }
@@ -49,22 +79,27 @@ impl Iterator for NumberScanner<'_> {
     type Item = u32;
     fn next(&mut self) -> Option<Self::Item> {
         let f_single_char = |current_char: char, _current_index| current_char.to_digit(BASE_10);
-        let f_lookback_str = |last_part: &str, _index_range| {
-            self.number_words
-                .get(last_part)
-                .map(|&number| u32::try_from(number).expect("single digit fits in u32"))
-        };
-        self.scanner
-            .find_next(Some(f_single_char), Some(f_lookback_str))
+        let f_lookback_str = self.number_words.as_ref().map(|number_words| {
+            |last_part: &str, _index_range| {
+                number_words
+                    .get(last_part)
+                    .map(|&number| u32::try_from(number).expect("single digit fits in u32"))
+            }
+        });
+        self.scanner.find_next(Some(f_single_char), f_lookback_str)
     }
 }
 
 fn sum_calibration_amended_by_a_very_young_elf(input: &str) -> u32 {
+    sum_calibration(input, Calibration::NumberWords)
+}
+
+fn sum_calibration(input: &str, calibration: Calibration) -> u32 {
     input
         .lines()
         .filter_map(|line| {
             // NOTE: a single digit on the line means *BOTH* first and last digits are that digit
-            let mut digits = NumberScanner::new(line);
+            let mut digits = NumberScanner::new(line, calibration);
             // let mut digits = line.chars().filter_map(|char| char.to_digit(BASE_10));
             let first_digit = digits.next()?;
             let last_digit = digits.last().unwrap_or(first_digit);
@@ -79,7 +114,7 @@ fn sum_calibration_amended_by_a_very_young_elf(input: &str) -> u32 {
 mod tests {
 
     fn test_fn(input: &str) -> u32 {
-        crate::sum_calibration_amended_by_a_very_young_elf(input)
+        crate::days::day1_trebuchet::sum_calibration_amended_by_a_very_young_elf(input)
     }
 
     #[test]
@@ -165,6 +200,17 @@ treb7uchet";
         assert_eq!(test_fn(input), 142);
     }
     #[test]
+    fn example_digits_only_ignores_words() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz";
+        let sum = crate::days::day1_trebuchet::sum_calibration(
+            input,
+            crate::days::day1_trebuchet::Calibration::DigitsOnly,
+        );
+        assert_eq!(sum, 11 + 22);
+    }
+    #[test]
     fn example_spelled_out() {
         let input = "two1nine
 eightwothree
@@ -175,4 +221,14 @@ zoneight234
 7pqrstsixteen";
         assert_eq!(test_fn(input), 281);
     }
+
+    #[test]
+    fn parse_error_location() {
+        let err =
+            crate::days::day1_trebuchet::check_calibration_chars("two1nine\neight-wo").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: unexpected character '-'"
+        );
+    }
 }
@@ -1,54 +1,89 @@
-fn main() -> anyhow::Result<()> {
-    println!("hello, so many cubes!");
-    let input = advent_2023::get_input_string()?;
+use crate::ParseError;
+use nom::error::VerboseErrorKind;
 
-    let Stats {
-        sum_valid_game_ids,
-        sum_of_powers,
-    } = play_cube_game(&input);
-    println!("Sum of IDs of valid games: {sum_valid_game_ids}");
-    println!("Sum of power for the minimal sets for all games: {sum_of_powers}");
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 2;
+    type Parsed = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_games(input)?)
+    }
+    fn part1(games: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(sum_valid_game_ids(games))
+    }
+    fn part2(games: &Self::Parsed) -> anyhow::Result<u32> {
+        Ok(sum_of_powers(games))
+    }
 }
 
+#[cfg(test)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct Stats {
     sum_valid_game_ids: u32,
     sum_of_powers: u32,
 }
 
+#[cfg(test)]
 fn play_cube_game(input: &str) -> Stats {
+    let games = parse_games(input).expect("valid input");
+    Stats {
+        sum_valid_game_ids: sum_valid_game_ids(&games),
+        sum_of_powers: sum_of_powers(&games),
+    }
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| {
+            let (rest, game) = parse::game(line).map_err(|err| {
+                let (message, rest) = match &err {
+                    // the innermost context names what was expected
+                    nom::Err::Error(err) | nom::Err::Failure(err) => err
+                        .errors
+                        .iter()
+                        .find_map(|(rest, kind)| match kind {
+                            VerboseErrorKind::Context(context) => Some((*context, *rest)),
+                            _ => None,
+                        })
+                        .unwrap_or(("invalid game", line)),
+                    nom::Err::Incomplete(_) => ("incomplete game", ""),
+                };
+                ParseError::new(message)
+                    .with_span(0..rest.chars().next().map_or(0, char::len_utf8))
+                    .at(line_index, line, rest)
+            })?;
+            if !rest.is_empty() {
+                return Err(ParseError::new("extra text after game").at(line_index, line, rest));
+            }
+            Ok(game)
+        })
+        .collect()
+}
+
+fn sum_valid_game_ids(games: &[Game]) -> u32 {
     const LIMIT: ColorCounts = ColorCounts {
         red: 12,
         green: 13,
         blue: 14,
     };
 
-    let games: Vec<_> = input
-        .lines()
-        .map(|line| {
-            let (input, game) = parse::game(line).expect("valid game line");
-            assert!(input.is_empty());
-            game
-        })
-        .collect();
-
-    let sum_of_powers = games.iter().map(|game| game.miminal_counts().power()).sum();
-
-    let sum_valid_game_ids = games
+    games
         .iter()
         .filter_map(|game| game.within_limits(LIMIT).then_some(game.game_id))
-        .sum();
+        .sum()
+}
 
-    Stats {
-        sum_valid_game_ids,
-        sum_of_powers,
-    }
+fn sum_of_powers(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.miminal_counts().power()).sum()
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Game {
+pub struct Game {
     pub game_id: u32,
     reveals: Vec<ColorCounts>,
 }
@@ -123,59 +158,63 @@ mod parse {
     use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::character::complete::digit1;
-    use nom::combinator::{map, map_res};
+    use nom::combinator::{cut, map, map_res, value};
+    use nom::error::{context, VerboseError};
     use nom::multi::separated_list1;
     use nom::sequence::tuple;
-    use nom::IResult;
 
-    use crate::{ColorCount, ColorCounts, Game};
+    use crate::days::day2_cube_conundrum::{ColorCount, ColorCounts, Game};
 
-    pub fn game(input: &str) -> IResult<&str, Game> {
-        let colon = tag(": ");
+    /// Results with the [`context`] of each failure, for the error message
+    type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+    pub fn game(input: &str) -> IResult<'_, Game> {
+        let colon = context("expected \": \" after the game id", tag(": "));
         map(tuple((game_id, colon, reveals)), |(game_id, _, reveals)| {
             Game { game_id, reveals }
         })(input)
     }
 
-    fn game_id(input: &str) -> IResult<&str, u32> {
-        let (input, _) = tag("Game ")(input)?;
-        map_res(digit1, str::parse)(input)
+    fn game_id(input: &str) -> IResult<'_, u32> {
+        let (input, _) = context("expected \"Game \"", tag("Game "))(input)?;
+        context("invalid game id", map_res(digit1, str::parse))(input)
     }
 
-    fn reveals(input: &str) -> IResult<&str, Vec<ColorCounts>> {
+    fn reveals(input: &str) -> IResult<'_, Vec<ColorCounts>> {
         separated_list1(tag("; "), reveal)(input)
     }
-    fn reveal(input: &str) -> IResult<&str, ColorCounts> {
-        map_res(
-            separated_list1(tag(", "), reveal_part),
-            ColorCounts::try_from_iter,
-        )(input)
+    fn reveal(input: &str) -> IResult<'_, ColorCounts> {
+        // NOTE: failures are cut, so a bad reveal after a separator is reported, not left over
+        cut(context(
+            "duplicate color in reveal",
+            map_res(
+                separated_list1(tag(", "), reveal_part),
+                ColorCounts::try_from_iter,
+            ),
+        ))(input)
     }
 
-    fn reveal_part(input: &str) -> IResult<&str, ColorCount> {
+    fn reveal_part(input: &str) -> IResult<'_, ColorCount> {
         const COLOR_RED: &str = "red";
         const COLOR_GREEN: &str = "green";
         const COLOR_BLUE: &str = "blue";
 
-        let (input, count) = map_res(digit1, str::parse)(input)?;
-        let (input, _) = tag(" ")(input)?;
-        let match_colors = alt((tag(COLOR_RED), tag(COLOR_GREEN), tag(COLOR_BLUE)));
-        map(match_colors, move |color| {
-            if color == COLOR_RED {
-                ColorCount::Red(count)
-            } else if color == COLOR_GREEN {
-                ColorCount::Green(count)
-            } else if color == COLOR_BLUE {
-                ColorCount::Blue(count)
-            } else {
-                panic!("color {color:?} matched tags, but not matching")
-            }
-        })(input)
+        let (input, count) = cut(context("invalid count", map_res(digit1, str::parse)))(input)?;
+        let (input, _) = cut(context("expected a space after the count", tag(" ")))(input)?;
+        let match_colors = alt((
+            value(ColorCount::Red as fn(u32) -> ColorCount, tag(COLOR_RED)),
+            value(ColorCount::Green as fn(u32) -> ColorCount, tag(COLOR_GREEN)),
+            value(ColorCount::Blue as fn(u32) -> ColorCount, tag(COLOR_BLUE)),
+        ));
+        map(
+            cut(context("expected color", match_colors)),
+            move |color_count| color_count(count),
+        )(input)
     }
 
     #[cfg(test)]
     mod tests {
-        use crate::{ColorCount, ColorCounts, Game};
+        use crate::days::day2_cube_conundrum::{ColorCount, ColorCounts, Game};
 
         #[test]
         fn game_id_number() {
@@ -280,7 +319,7 @@ mod parse {
 
 #[cfg(test)]
 mod tests {
-    use crate::Stats;
+    use crate::days::day2_cube_conundrum::{parse_games, Stats};
 
     #[test]
     fn example() {
@@ -299,4 +338,31 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
             }
         );
     }
+
+    #[test]
+    fn parse_error_location() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue; 2 purple";
+        let err = parse_games(input).unwrap_err();
+        let location = err.location().expect("located");
+        assert_eq!((location.line, location.column), (2, 19));
+        assert_eq!(location.text, "p");
+        assert_eq!(err.to_string(), "line 2, column 19: expected color");
+
+        let err = parse_games("Game x: 3 blue").unwrap_err();
+        let location = err.location().expect("located");
+        assert_eq!((location.column, location.text.as_str()), (6, "x"));
+        assert_eq!(err.to_string(), "line 1, column 6: invalid game id");
+
+        let err = parse_games("Game 1: 99999999999999999999 red").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 9: invalid count");
+        let err = parse_games("Game 1: 2 red, 3 red").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 9: duplicate color in reveal"
+        );
+        let err = parse_games("Game 1: 2 red;").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 14: extra text after game");
+        let err = parse_games("garbage").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected \"Game \"");
+    }
 }
@@ -1,23 +1,21 @@
-use std::collections::HashMap;
-
-use entity_scanner::EntityScanner;
-
-fn main() -> anyhow::Result<()> {
-    println!("hello engine-fixing elf!");
+//! Alternate engine for day 3, see [`super::day3_simpler`] for the solution used by the runner
 
-    let input = advent_2023::get_input_string()?;
-    let Stats { part_numbers_sum } = interpret_engine_schematic(&input);
+use std::collections::HashMap;
 
-    println!("Sum of part numbers: {part_numbers_sum}");
+use crate::{
+    log::{self, Level},
+    log_debug,
+    point::Point,
+};
 
-    Ok(())
-}
+use entity_scanner::EntityScanner;
 
 #[derive(Debug)]
-struct Stats {
-    part_numbers_sum: u32,
+pub struct Stats {
+    pub part_numbers_sum: u32,
+    pub gear_ratios_sum: u32,
 }
-fn interpret_engine_schematic(input: &str) -> Stats {
+pub fn interpret_engine_schematic(input: &str) -> Stats {
     let entities: Vec<_> = input
         .lines()
         .enumerate()
@@ -25,10 +23,10 @@ fn interpret_engine_schematic(input: &str) -> Stats {
         .collect();
 
     // DEBUG print entities
-    if false {
+    if log::enabled(Level::Trace) {
         let mut lines_iter = input.lines().enumerate().peekable();
         let print_line = |(line_number, line)| {
-            println!("LINE {:03}: {line}", line_number);
+            eprintln!("LINE {:03}: {line}", line_number);
         };
         for entity in &entities {
             while let Some(true) = lines_iter.peek().map(|&(index, _)| entity.row() >= index) {
@@ -37,8 +35,8 @@ fn interpret_engine_schematic(input: &str) -> Stats {
             let entity_col_offset = entity.col_start();
             let padding = " ".to_string().repeat(entity_col_offset + 10);
             match entity {
-                Entity::Number(number) => println!("{padding}{number}"),
-                Entity::Symbol(symbol) => println!("{padding}{symbol}"),
+                Entity::Number(number) => eprintln!("{padding}{number}"),
+                Entity::Symbol(symbol) => eprintln!("{padding}{symbol}"),
             }
         }
         for line_elem in lines_iter {
@@ -56,7 +54,7 @@ fn interpret_engine_schematic(input: &str) -> Stats {
     }
 
     // DEBUG print entities
-    if true {
+    if log::enabled(Level::Debug) {
         let lines: Vec<_> = input.lines().collect();
         let print_lines = |row: usize| {
             for (n, line) in lines
@@ -65,7 +63,7 @@ fn interpret_engine_schematic(input: &str) -> Stats {
                 .take(row + 2)
                 .skip(row.saturating_sub(1))
             {
-                println!("LINE {n:03}: {line}");
+                eprintln!("LINE {n:03}: {line}");
             }
         };
         let mut prev_line_printed = None;
@@ -83,9 +81,9 @@ fn interpret_engine_schematic(input: &str) -> Stats {
                         }
                     }
                     let value = number.value;
-                    println!("{padding}{value} NOT adjacent");
+                    eprintln!("{padding}{value} NOT adjacent");
                 }
-                // Entity::Symbol(symbol) => println!("{padding}{symbol}"),
+                // Entity::Symbol(symbol) => eprintln!("{padding}{symbol}"),
                 _ => {}
             }
         }
@@ -98,7 +96,7 @@ fn interpret_engine_schematic(input: &str) -> Stats {
                 if number.region.adjacent_to_symbol(&symbols_by_row) {
                     Some(number.value)
                 } else {
-                    println!("NOT ADJACENT: {number}");
+                    log_debug!("NOT ADJACENT: {number}");
                     None
                 }
             }
@@ -106,13 +104,41 @@ fn interpret_engine_schematic(input: &str) -> Stats {
         })
         .sum();
 
-    Stats { part_numbers_sum }
+    // a gear is an asterisk adjacent to exactly two part numbers
+    let mut numbers_by_asterisk: HashMap<Location, Vec<u32>> = HashMap::new();
+    for entity in &entities {
+        let Entity::Number(number) = entity else {
+            continue;
+        };
+        for symbol in number.region.adjacent_symbols(&symbols_by_row) {
+            if symbol.value == GEAR_CHAR {
+                numbers_by_asterisk
+                    .entry(symbol.location)
+                    .or_default()
+                    .push(number.value);
+            }
+        }
+    }
+    let gear_ratios_sum = numbers_by_asterisk
+        .values()
+        .filter_map(|numbers| match numbers[..] {
+            [first, second] => Some(first * second),
+            _ => None,
+        })
+        .sum();
+
+    Stats {
+        part_numbers_sum,
+        gear_ratios_sum,
+    }
 }
 
+const GEAR_CHAR: char = '*';
+
 mod entity_scanner {
-    use advent_2023::{CharIndex, CharIndexEnd, CharScanner};
+    use crate::{CharIndex, CharIndexEnd, CharScanner};
 
-    use crate::{Entity, Location, Number, Region, Symbol};
+    use crate::days::day3_gear_ratios::{Entity, Location, Number, Region, Symbol};
 
     const EMPTY_CHAR: char = '.';
     const BASE_10: u32 = 10;
@@ -153,7 +179,24 @@ mod entity_scanner {
                     let (value, (start_sequence, end_sequence)) = {
                         let mut chars = last_str.chars().peekable();
                         let char_first = chars.next()?;
-                        let char_last = chars.next_back()?;
+                        let Some(char_last) = chars.next_back() else {
+                            // case: entire line is a single digit
+                            let entire_line = start_index.is_zero() && end_index.is_end();
+                            let value = char_first.to_digit(BASE_10).filter(|_| entire_line)?;
+                            return Some(Entity::Number(Number {
+                                value,
+                                region: Region {
+                                    top_left: Location {
+                                        row,
+                                        col_sequence: start_index.sequence(),
+                                    },
+                                    bottom_right: Location {
+                                        row,
+                                        col_sequence: end_index.sequence(),
+                                    },
+                                },
+                            }));
+                        };
                         // NOTE 1-digit case is already handled (e.g. ".9." len=3)
                         let first_is_digit = is_digit(char_first);
                         let last_is_digit = is_digit(char_last);
@@ -218,7 +261,7 @@ mod entity_scanner {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct Location {
     /// Line number
     row: usize,
@@ -232,36 +275,45 @@ struct Region {
 }
 impl Region {
     fn adjacent_to_symbol(&self, symbols_by_row: &HashMap<usize, Vec<Symbol>>) -> bool {
-        let adjacent_rows = {
-            let row_start = self.top_left.row.saturating_sub(1);
-            let row_end = self.bottom_right.row.saturating_add(1);
-            // ROWS are closed (inclusive of end)
-            row_start..=row_end
-        };
-
-        let adjacent_cols = {
-            let col_start = self.top_left.col_sequence.saturating_sub(1);
-            let col_end = self.bottom_right.col_sequence.saturating_add(1);
-            // COLUMNS are half-open (non-inclusive of end)
-            col_start..col_end
-        };
-
-        // println!("Region checking for rows {adjacent_rows:?}, cols {adjacent_cols:?}");
-        for row in adjacent_rows.clone() {
-            let Some(row_symbols) = symbols_by_row.get(&row) else {
-                continue;
-            };
-            for symbol in row_symbols {
-                let col = symbol.location.col_sequence;
-                if adjacent_cols.contains(&col) {
-                    // println!("\tSymbol matches! {symbol}");
-                    return true;
-                    // } else {
-                    //     println!("\tNOT adjacent.. {symbol}");
-                }
-            }
-        }
-        false
+        self.adjacent_symbols(symbols_by_row).next().is_some()
+    }
+    /// Returns each symbol adjacent to the region (once, even if next to several points)
+    fn adjacent_symbols<'a>(
+        &self,
+        symbols_by_row: &'a HashMap<usize, Vec<Symbol>>,
+    ) -> impl Iterator<Item = &'a Symbol> + 'a {
+        let region = *self;
+        let mut neighbors: Vec<Point> = self
+            .points()
+            .flat_map(Point::neighbors8)
+            .filter(|&point| !region.contains(point))
+            .collect();
+        neighbors.sort_by_key(|point| (point.row, point.col));
+        neighbors.dedup();
+        neighbors.into_iter().filter_map(move |point| {
+            symbols_by_row.get(&point.row)?.iter().find(|symbol| {
+                let Location { row, col_sequence } = symbol.location;
+                (row, col_sequence) == (point.row, point.col)
+            })
+        })
+    }
+    fn contains(&self, point: Point) -> bool {
+        let Self {
+            top_left,
+            bottom_right,
+        } = *self;
+        (top_left.row..=bottom_right.row).contains(&point.row)
+            && (top_left.col_sequence..bottom_right.col_sequence).contains(&point.col)
+    }
+    /// Returns each point in the region (columns are half-open, non-inclusive of end)
+    fn points(&self) -> impl Iterator<Item = Point> {
+        let Self {
+            top_left,
+            bottom_right,
+        } = *self;
+        (top_left.row..=bottom_right.row).flat_map(move |row| {
+            (top_left.col_sequence..bottom_right.col_sequence).map(move |col| Point { row, col })
+        })
     }
 }
 
@@ -324,7 +376,7 @@ impl std::fmt::Display for Symbol {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Entity, EntityScanner, Location, Number, Region, Symbol};
+    use crate::days::day3_gear_ratios::{Entity, EntityScanner, Location, Number, Region, Symbol};
 
     fn region(row: usize, (col_start, col_end): (usize, usize)) -> Region {
         Region {
@@ -474,6 +526,20 @@ mod tests {
         }
     }
     #[test]
+    fn number_single_digit_line() {
+        for row in 0..5 {
+            let line = "7";
+            let entities: Vec<_> = EntityScanner::new(row, line).collect();
+            assert_eq!(
+                entities,
+                vec![Entity::Number(Number {
+                    value: 7,
+                    region: region(row, (0, 1)),
+                })]
+            );
+        }
+    }
+    #[test]
     fn number_end_by_symbol() {
         for row in 0..5 {
             let line = "..59%";
@@ -497,6 +563,34 @@ mod tests {
         }
     }
     #[test]
+    fn number_after_symbol() {
+        for row in 0..5 {
+            let line = "#1*23";
+            let entities: Vec<_> = EntityScanner::new(row, line).collect();
+            let symbol = |value, col_sequence| {
+                Entity::Symbol(Symbol {
+                    value,
+                    location: Location { row, col_sequence },
+                })
+            };
+            assert_eq!(
+                entities,
+                vec![
+                    symbol('#', 0),
+                    symbol('*', 2),
+                    Entity::Number(Number {
+                        value: 1,
+                        region: region(row, (1, 2)),
+                    }),
+                    Entity::Number(Number {
+                        value: 23,
+                        region: region(row, (3, 5)),
+                    }),
+                ]
+            );
+        }
+    }
+    #[test]
     fn complex_lines() {
         let input = "...$.
 12345
@@ -568,6 +662,7 @@ fn sample_test() {
 .664.598..";
     let stats = interpret_engine_schematic(input);
     assert_eq!(stats.part_numbers_sum, 4361);
+    assert_eq!(stats.gear_ratios_sum, 467835);
 }
 
 #[test]
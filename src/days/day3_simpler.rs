@@ -1,32 +1,34 @@
 use std::collections::BTreeMap;
 
-use advent_2023::{
+use crate::{
+    log::{self, Level},
+    log_debug, log_trace,
     print::{ConsolePrinter, Highlight},
-    CharIndex, CharIndexEnd, CharIndices,
+    CharIndex, CharIndexEnd, CharIndices, ParseError,
 };
 
-const DEBUG_PRINT: bool = cfg!(test);
-
-fn main() -> anyhow::Result<()> {
-    println!("hello engine-fixing elf!");
-
-    let input = advent_2023::get_input_string()?;
-
-    let term = console::Term::buffered_stderr();
-
-    let Stats {
-        part_numbers_sum,
-        gear_ratios_sum,
-    } = interpret_engine_schematic(&input, term)?;
-
-    println!("Sum of part numbers: {part_numbers_sum}");
-    println!("Sum of gear ratios: {gear_ratios_sum}");
-
-    Ok(())
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 3;
+    /// Both sums are found in the same pass over the schematic
+    type Parsed = Stats;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let term = log::enabled(Level::Debug).then(console::Term::buffered_stderr);
+        interpret_engine_schematic(input, term)
+    }
+    fn part1(stats: &Stats) -> anyhow::Result<u32> {
+        Ok(stats.part_numbers_sum)
+    }
+    fn part2(stats: &Stats) -> anyhow::Result<u32> {
+        Ok(stats.gear_ratios_sum)
+    }
 }
 
 #[derive(Debug)]
-struct Stats {
+pub struct Stats {
     part_numbers_sum: u32,
     gear_ratios_sum: u32,
 }
@@ -40,9 +42,7 @@ fn interpret_engine_schematic_inner(
     input: &str,
     mut console_printer: ConsolePrinter,
 ) -> anyhow::Result<Stats> {
-    if DEBUG_PRINT {
-        println!("------------------------------");
-    }
+    log_trace!("------------------------------");
     let mut lines = input.lines().enumerate().peekable();
 
     let mut part_numbers_sum = 0;
@@ -50,8 +50,10 @@ fn interpret_engine_schematic_inner(
 
     let mut line_prev = None;
     while let Some((row, line)) = lines.next() {
-        if DEBUG_PRINT {
-            println!("{line:?}");
+        log_trace!("{line:?}");
+        if let Some((index, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_graphic()) {
+            let text = &line[index..(index + c.len_utf8())];
+            return Err(ParseError::unexpected_char(c).at(row, line, text).into());
         }
         let mut chars = CharIndices::new(line);
         let line_next = lines.peek().copied();
@@ -89,7 +91,7 @@ fn interpret_engine_schematic_inner(
             let number_str = number_start
                 .slice_string(number_end.0, line)
                 .expect("in bounds of current line");
-            let number = parse_int(number_str)?;
+            let number = parse_int(number_str).map_err(|err| err.at(row, line, number_str))?;
 
             let mut asterisk_positions = vec![];
 
@@ -112,7 +114,15 @@ fn interpret_engine_schematic_inner(
             }
 
             let line_search_start = before_number_start.map_or(number_start, |(col, _)| col);
-            let line_search_end = after_number_end.map_or(number_end.0, CharIndexEnd::Position);
+            let line_search_end = match (after_number_end, number_end.1) {
+                (Some(col), _) => CharIndexEnd::Position(col),
+                // the char after the number ends the line, so search through the end
+                (None, Some(_)) => chars
+                    .take_end_char_sequence()
+                    .map(CharIndexEnd::End)
+                    .expect("exhausted chars"),
+                (None, None) => number_end.0,
+            };
 
             // search previous line
             let (span_prev_line, count_prev_line) = count_symbols(
@@ -149,27 +159,26 @@ fn interpret_engine_schematic_inner(
                 style: debug_style,
             });
 
-            if DEBUG_PRINT {
+            if log::enabled(Level::Trace) {
                 let symbol_before = before_number_start.map(|(_, c)| c);
                 let symbol_after = number_end.1;
 
                 let show_prev_next_lines = line_prev.is_some() || line_next.is_some();
                 if show_prev_next_lines {
-                    println!("        {span_prev_line}");
+                    eprintln!("        {span_prev_line}");
                 }
-                println!(
+                eprintln!(
                     "Number: {char_before}{number_str}{char_after}",
                     char_before = opt_char_to_str(symbol_before),
                     char_after = opt_char_to_str(symbol_after),
                 );
                 if show_prev_next_lines {
-                    println!("        {span_next_line}");
+                    eprintln!("        {span_next_line}");
                 }
-                println!(" -> value {number}, {count} symbols,  ==> sum {part_numbers_sum}");
+                eprintln!(" -> value {number}, {count} symbols,  ==> sum {part_numbers_sum}");
             }
 
-            if let Some(pos) = asterisk_positions.pop() {
-                assert!(asterisk_positions.is_empty());
+            for pos in asterisk_positions {
                 let list = part_numbers_by_asterisk.entry(pos).or_default();
                 list.push(number);
             }
@@ -194,15 +203,15 @@ fn interpret_engine_schematic_inner(
         }
         let InputPosition { row, col } = pos;
         let col = col.map_or(0, |c_index| c_index.sequence());
-        println!("Line {row}, col {col} connects numbers {numbers:?}");
+        log_debug!("Line {row}, col {col} connects numbers {numbers:?}");
     }
 
+    // a gear is an asterisk adjacent to exactly two part numbers
     let gear_ratios_sum = part_numbers_by_asterisk
-        .into_iter()
-        .map(|(pos, numbers)| match numbers.len() {
-            0 | 1 => 0,
-            2 => numbers.into_iter().product(),
-            count => panic!("{count} (more than 2) numbers around asterisk at {pos:?}"),
+        .into_values()
+        .map(|numbers| match numbers[..] {
+            [first, second] => first * second,
+            _ => 0,
         })
         .sum();
 
@@ -216,8 +225,8 @@ const BASE_10: u32 = 10;
 fn is_digit(c: char) -> bool {
     c.is_digit(BASE_10)
 }
-fn parse_int(s: &str) -> anyhow::Result<u32> {
-    Ok(u32::from_str_radix(s, BASE_10)?)
+fn parse_int(s: &str) -> Result<u32, ParseError> {
+    u32::from_str_radix(s, BASE_10).map_err(|err| ParseError::new(format!("invalid number: {err}")))
 }
 fn count_symbol(c: char) -> u32 {
     if !is_digit(c) && c != '.' {
@@ -269,7 +278,8 @@ struct InputPosition {
 
 #[cfg(test)]
 mod tests {
-    use crate::interpret_engine_schematic;
+    use crate::days::{day3_gear_ratios, day3_simpler::interpret_engine_schematic};
+    use proptest::prelude::*;
 
     #[test]
     fn sample_test() {
@@ -343,4 +353,83 @@ mod tests {
             ".123*40" => 123+40;
         };
     }
+
+    #[test]
+    fn numbers_sharing_asterisks() {
+        // 2 touches both asterisks, and the right asterisk touches three numbers
+        let input = "1.2.3
+.*.*.
+...4.";
+        let stats = interpret_engine_schematic(input, None).unwrap();
+        assert_eq!(stats.part_numbers_sum, 10);
+        assert_eq!(stats.gear_ratios_sum, 2);
+    }
+
+    #[test]
+    fn symbol_diagonal_past_line_end() {
+        let input = ".*
+1.";
+        let stats = interpret_engine_schematic(input, None).unwrap();
+        assert_eq!(stats.part_numbers_sum, 1);
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let err = interpret_engine_schematic("467..\n..*\t.", None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: unexpected character '\\t'"
+        );
+        let err = interpret_engine_schematic("..99999999999*", None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: invalid number: number too large to fit in target type"
+        );
+    }
+
+    /// Schematic lines of equal width, with at most 3 digits in each number
+    fn arb_schematic() -> impl Strategy<Value = String> {
+        let cell = prop_oneof![
+            6 => Just('.'),
+            4 => prop::char::range('0', '9'),
+            1 => Just('*'),
+            1 => prop::sample::select(vec!['#', '$', '+', '/', '@']),
+        ];
+        (1..12_usize, 1..8_usize)
+            .prop_flat_map(move |(width, height)| {
+                prop::collection::vec(prop::collection::vec(cell.clone(), width), height)
+            })
+            .prop_map(|rows| {
+                let lines: Vec<String> = rows
+                    .into_iter()
+                    .map(|mut row| {
+                        let mut digits = 0;
+                        for c in &mut row {
+                            digits = if c.is_ascii_digit() { digits + 1 } else { 0 };
+                            if digits > 3 {
+                                *c = '.';
+                                digits = 0;
+                            }
+                        }
+                        row.into_iter().collect()
+                    })
+                    .collect();
+                lines.join("\n")
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(5000))]
+        #[test]
+        fn matches_gear_ratios_engine(schematic in arb_schematic()) {
+            let simpler = interpret_engine_schematic(&schematic, None).unwrap();
+            let alternate = day3_gear_ratios::interpret_engine_schematic(&schematic);
+            prop_assert_eq!(
+                (simpler.part_numbers_sum, simpler.gear_ratios_sum),
+                (alternate.part_numbers_sum, alternate.gear_ratios_sum),
+                "schematic:\n{}",
+                schematic
+            );
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use crate::{log_trace, ParseError};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 4;
+    type Parsed = CardWins;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_card_wins(input)?)
+    }
+    fn part1(card_wins: &CardWins) -> anyhow::Result<u32> {
+        Ok(card_wins.total_score())
+    }
+    fn part2(card_wins: &CardWins) -> anyhow::Result<usize> {
+        Ok(card_wins.number_of_cards())
+    }
+}
+
+/// Count of matching numbers for each card
+#[derive(Debug)]
+pub struct CardWins(Vec<u32>);
+
+fn parse_card_wins(input: &str) -> Result<CardWins, ParseError> {
+    let card_wins = input
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| {
+            let Some(colon_position) = line.find(':') else {
+                return Err(
+                    ParseError::new("no colon after the card id").at(line_index, line, line)
+                );
+            };
+            let Some(pipe_position) = line.find('|') else {
+                return Err(
+                    ParseError::new("no pipe between the number lists").at(line_index, line, line)
+                );
+            };
+            if pipe_position < colon_position {
+                return Err(ParseError::new("pipe before the colon").at(
+                    line_index,
+                    line,
+                    &line[pipe_position..=pipe_position],
+                ));
+            }
+            let parse_numbers = |text: &str| {
+                text.split_whitespace()
+                    .map(|number| {
+                        number.parse::<u32>().map_err(|err| {
+                            ParseError::new(format!("invalid number: {err}"))
+                                .at(line_index, line, number)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let winning_numbers = {
+                let mut winning_numbers =
+                    parse_numbers(&line[(colon_position + 1)..pipe_position])?;
+                winning_numbers.sort();
+                winning_numbers
+            };
+
+            let current_numbers = parse_numbers(&line[(pipe_position + 1)..])?;
+
+            let matching_count = current_numbers
+                .iter()
+                .filter(|&n| winning_numbers.binary_search(n).is_ok())
+                .count();
+            let matching_count = u32::try_from(matching_count).expect("matching_count fits in u32");
+            Ok(matching_count)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CardWins(card_wins))
+}
+
+impl CardWins {
+    fn total_score(&self) -> u32 {
+        self.0
+            .iter()
+            .map(|&matching_count| {
+                if let Some(matches_less_one) = matching_count.checked_sub(1) {
+                    2u32.pow(matches_less_one)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+    fn number_of_cards(&self) -> usize {
+        let Self(card_wins) = self;
+        let card_wins_len = card_wins.len();
+        let mut card_multipliers: Vec<_> = std::iter::repeat_n(1, card_wins_len).collect();
+        for (this_game, score) in card_wins.iter().enumerate() {
+            let score = usize::try_from(*score).expect("score fits in usize");
+            let this_multiplier = card_multipliers[this_game];
+            log_trace!("card {this_game}: {score}");
+            for next_game in (0..score).filter_map(|relative| {
+                let absolute = relative + 1 + this_game;
+                (absolute < card_wins_len).then_some(absolute)
+            }) {
+                let orig = card_multipliers[next_game];
+                log_trace!("\tcard {next_game} += {this_multiplier} (was {orig})");
+                card_multipliers[next_game] += this_multiplier;
+            }
+        }
+        card_multipliers.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_card_wins;
+
+    #[test]
+    fn parse_error_location() {
+        let err = parse_card_wins("garbage").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: no colon after the card id"
+        );
+
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30";
+        let err = parse_card_wins(input).unwrap_err();
+        let location = err.location().expect("located");
+        assert_eq!((location.line, location.column), (2, 12));
+        assert_eq!(location.text, "3x");
+    }
+}
@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::{
+    log_debug, log_trace,
+    ranges::{PiecewiseMap, RangeSet},
+    ParseError,
+};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 5;
+    type Parsed = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Almanac::new(input)
+    }
+    fn part1(almanac: &Almanac) -> anyhow::Result<u64> {
+        almanac.closest_seed_location()
+    }
+    fn part2(almanac: &Almanac) -> anyhow::Result<u64> {
+        almanac.closest_seed_range_location()
+    }
+}
+
+#[cfg(test)]
+fn get_closest_location(input: &str) -> anyhow::Result<u64> {
+    Almanac::new(input)?.closest_seed_range_location()
+}
+
+const START_UNIT_SEED: &str = "seed";
+const END_UNIT_LOCATION: &str = "location";
+
+pub struct Almanac {
+    seeds_entries: Vec<u64>,
+    maps: HashMap<String, MapUnit>,
+}
+impl Almanac {
+    fn new(input: &str) -> anyhow::Result<Self> {
+        let mut input_lines = input.lines().enumerate();
+
+        let Some((_, seeds_line)) = input_lines.next() else {
+            anyhow::bail!("missing seeds line")
+        };
+        let Some((blank_index, blank_line)) = input_lines.next() else {
+            anyhow::bail!("missing maps after seeds line")
+        };
+        if !blank_line.is_empty() {
+            anyhow::bail!(ParseError::new("blank line is not blank").at(
+                blank_index,
+                blank_line,
+                blank_line
+            ));
+        }
+
+        let seeds_entries = parse_seeds(0, seeds_line)?;
+        let maps = parse_maps(input_lines)?;
+        log_debug!("Loaded maps. Seed entries {seeds_entries:?}");
+
+        Ok(Self {
+            seeds_entries,
+            maps,
+        })
+    }
+    /// Closest location for the seeds listed individually
+    fn closest_seed_location(&self) -> anyhow::Result<u64> {
+        let locations = self
+            .seeds_entries
+            .iter()
+            .map(|&seed| {
+                let mut current_value = seed;
+                let mut current_kind = START_UNIT_SEED;
+                while current_kind != END_UNIT_LOCATION {
+                    let Some(map_unit) = self.maps.get(current_kind) else {
+                        anyhow::bail!("map not found for {current_kind:?}")
+                    };
+                    let Some(next_value) = map_unit.map.apply(current_value) else {
+                        anyhow::bail!("value {current_value} mapped out of bounds")
+                    };
+                    current_value = next_value;
+                    current_kind = &map_unit.output_kind;
+                }
+                Ok(current_value)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let Some(closest_location) = locations.into_iter().min() else {
+            anyhow::bail!("no seeds provided")
+        };
+        Ok(closest_location)
+    }
+    /// Closest location for the seeds listed as pairs of (start, length)
+    fn closest_seed_range_location(&self) -> anyhow::Result<u64> {
+        let Self {
+            seeds_entries,
+            maps,
+        } = self;
+
+        let pairs = seeds_entries.chunks_exact(2);
+        if let [unpaired] = pairs.remainder() {
+            anyhow::bail!("seed {unpaired} has no range length")
+        }
+        let seed_ranges: RangeSet<u64> = pairs
+            .map(|pair| {
+                let [start, len] = [pair[0], pair[1]];
+                let Some(end) = start.checked_add(len) else {
+                    anyhow::bail!("seed range {start} of length {len} ends past {}", u64::MAX)
+                };
+                Ok(start..end)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        log_debug!("Seeds are defined by ranges {seed_ranges:?}");
+
+        // all maps combined, from seed to location
+        let combined_map = MapUnit::from_map_set(maps, (START_UNIT_SEED, END_UNIT_LOCATION))?;
+        log_trace!("simplified the map: {combined_map:?}");
+
+        let locations = combined_map.apply_set(&seed_ranges);
+        let Some(closest_location) = locations.first() else {
+            anyhow::bail!("no seeds provided")
+        };
+
+        Ok(closest_location)
+    }
+}
+
+fn parse_seeds(line_index: usize, line: &str) -> Result<Vec<u64>, ParseError> {
+    const SEEDS_COLON: &str = "seeds: ";
+    let Some((empty, numbers_str)) = line.split_once(SEEDS_COLON) else {
+        return Err(
+            ParseError::new(format!("not found: {SEEDS_COLON:?}")).at(line_index, line, line)
+        );
+    };
+    if !empty.is_empty() {
+        return Err(ParseError::new("extra text before seeds label").at(line_index, line, empty));
+    }
+    parse_numbers(line_index, line, numbers_str)
+}
+
+/// Parses whitespace-separated numbers, locating the first invalid number within the line
+fn parse_numbers(line_index: usize, line: &str, text: &str) -> Result<Vec<u64>, ParseError> {
+    text.split_whitespace()
+        .map(|number| {
+            u64::from_str(number).map_err(|err| {
+                ParseError::new(format!("invalid number: {err}")).at(line_index, line, number)
+            })
+        })
+        .collect()
+}
+
+fn parse_maps<'a>(
+    mut input_lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<HashMap<String, MapUnit>, ParseError> {
+    let mut map_units = HashMap::new();
+    while let Some((title_index, title_line)) = input_lines.next() {
+        let (title_from, title_to) = parse_title_line(title_line)
+            .map_err(|err| err.at(title_index, title_line, title_line))?;
+
+        let mut ranges = vec![];
+        for (line_index, line) in input_lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            let range = parse_range(line_index, line)?;
+            ranges.push(range);
+        }
+
+        let Some(map) = PiecewiseMap::new(ranges) else {
+            return Err(ParseError::new("overlapping source ranges in map").at(
+                title_index,
+                title_line,
+                title_line,
+            ));
+        };
+        let map_unit = MapUnit {
+            map,
+            output_kind: title_to,
+        };
+
+        map_units.insert(title_from, map_unit);
+    }
+    Ok(map_units)
+}
+/// Returns an unlocated error, for the caller to locate on the title line
+fn parse_title_line(title_line: &str) -> Result<(String, String), ParseError> {
+    const MAP_COLON: &str = "map:";
+    let Some((title_map_str, map_colon)) = title_line.split_once(' ') else {
+        return Err(ParseError::new("expected map declaration to have space"));
+    };
+    if map_colon != MAP_COLON {
+        return Err(ParseError::new(format!(
+            "expected map declaration to end with {MAP_COLON:?}, found {map_colon:?}"
+        ))
+        .with_span(title_map_str.len() + 1..title_line.len()));
+    }
+    let mut title_parts = title_map_str.split('-');
+    let Some(a) = title_parts.next() else {
+        return Err(ParseError::new("missing map part A"));
+    };
+    match title_parts.next() {
+        Some("to") => {}
+        Some(unexpected) => {
+            return Err(ParseError::new(format!(
+                "unexpected token in map title: {unexpected:?}"
+            )))
+        }
+        None => return Err(ParseError::new("incomplete map title")),
+    }
+    let Some(b) = title_parts.next() else {
+        return Err(ParseError::new("missing map part B"));
+    };
+    Ok((a.to_string(), b.to_string()))
+}
+
+/// Returns the source range and the offset to the destination
+fn parse_range(line_index: usize, line: &str) -> Result<(Range<u64>, i64), ParseError> {
+    let numbers = parse_numbers(line_index, line, line)?;
+    let Ok([dest_start, source_start, len]) = <[u64; 3]>::try_from(numbers) else {
+        return Err(
+            ParseError::new("expected 3 numbers to define range").at(line_index, line, line)
+        );
+    };
+
+    let too_large = |label: &str, value: u64| {
+        ParseError::new(format!("{label} {value} too large")).at(line_index, line, line)
+    };
+    let offset = {
+        let Ok(dest_start) = i64::try_from(dest_start) else {
+            return Err(too_large("destination start", dest_start));
+        };
+        let Ok(source_start) = i64::try_from(source_start) else {
+            return Err(too_large("source start", source_start));
+        };
+
+        dest_start - source_start
+    };
+
+    let Some(source_end) = source_start.checked_add(len) else {
+        return Err(ParseError::new("range end too large").at(line_index, line, line));
+    };
+    let sources = source_start..source_end;
+
+    Ok((sources, offset))
+}
+
+#[derive(Clone, Debug)]
+pub struct MapUnit {
+    map: PiecewiseMap,
+    output_kind: String,
+}
+impl MapUnit {
+    /// Combines the maps from the start unit to the end unit, in order
+    fn from_map_set(
+        maps: &HashMap<String, MapUnit>,
+        (start_unit, end_unit): (&str, &str),
+    ) -> anyhow::Result<PiecewiseMap> {
+        let mut combined = PiecewiseMap::default();
+        let mut current_kind = start_unit;
+        while current_kind != end_unit {
+            let Some(map_unit) = maps.get(current_kind) else {
+                anyhow::bail!("map not found for {current_kind:?}")
+            };
+            combined = combined.then(&map_unit.map);
+            current_kind = &map_unit.output_kind;
+        }
+        Ok(combined)
+    }
+}
+
+impl std::fmt::Display for MapUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { map, output_kind } = self;
+        write!(f, "{map:?} -> output {output_kind:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use crate::days::day5_seed_locations::{get_closest_location, Almanac};
+    use crate::ranges::PiecewiseMap;
+
+    #[test]
+    fn sample_input() {
+        // NOTE: format is [DEST] [SOURCE] [LEN]
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+        let closest = get_closest_location(input).unwrap();
+        assert_eq!(closest, 46);
+
+        let closest_individual = Almanac::new(input)
+            .unwrap()
+            .closest_seed_location()
+            .unwrap();
+        assert_eq!(closest_individual, 35);
+    }
+
+    //     #[test]
+    //     fn simpler_input() {
+    //         // NOTE: format is [DEST] [SOURCE] [LEN]
+    //         let input = "seeds: 79 9999999
+    //
+    // seed-to-soil map:
+    // 50 98 2
+    // 52 50 48
+    //
+    // soil-to-fertilizer map:
+    // 0 15 37
+    // 37 52 2
+    // 39 0 15
+    //
+    // fertilizer-to-location map:
+    // 60 56 37
+    // 56 93 4";
+    //         let closest = get_closest_location(input).unwrap();
+    //         assert_eq!(closest, 35); // TODO why is this 39 outside both the input and output ranges?
+    //     }
+
+    #[test]
+    fn single_layer() {
+        // NOTE: format is [DEST] [SOURCE] [LEN]
+        let input = "seeds: 25 50
+
+seed-to-location map:
+30 20 15";
+        // Maps 20..35 -> 30..45
+        let closest = get_closest_location(input).unwrap();
+        assert_eq!(closest, 35);
+    }
+
+    fn map(pieces: Vec<(Range<u64>, i64)>) -> PiecewiseMap {
+        PiecewiseMap::new(pieces).expect("no overlapping ranges")
+    }
+
+    #[test]
+    fn adding_maps_one_to_one() {
+        let map1 = map(vec![(5..10, 2), (10..20, 50), (30..40, -10)]);
+        let map2 = map(vec![(7..12, 10), (60..70, 100), (20..30, 9000)]);
+        let result = map1.then(&map2);
+        assert_eq!(
+            result.pieces(),
+            [
+                (5..10, 12),
+                (10..20, 150),
+                (20..30, 9000), // only map2
+                (30..40, 8990),
+                (60..70, 100), // only map2
+            ]
+        );
+    }
+
+    #[test]
+    fn adding_maps_splits() {
+        let map1 = map(vec![(10..30, 20)]);
+        let map2 = map(vec![
+            (5..40, 2),    // NOTE: maps to -15..20
+            (45..70, 100), // NOTE: maps to 25..50
+        ]);
+        let result = map1.then(&map2);
+        assert_eq!(
+            result.pieces(),
+            [
+                (5..10, 2),    // only map2
+                (10..20, 22),  // map1 & map2
+                (20..25, 20),  // only map1
+                (25..30, 120), // map1 & map2
+                (30..40, 2),   // only map2
+                // nothing 40..45
+                (45..70, 100), // only map2
+            ]
+        );
+    }
+
+    #[test]
+    fn adding_maps_joins() {
+        let map1 = map(vec![(10..30, 5), (30..40, 100)]);
+        let map2 = map(vec![
+            (0..135, 50),   // NOTE: maps to 5..95 and also 130..135 and onward
+            (135..200, 20), // NOTE: maps to 135..140 and onward
+        ]);
+        let result = map1.then(&map2);
+        assert_eq!(
+            result.pieces(),
+            [
+                (0..10, 50), // only map2
+                (10..30, 55),
+                (30..35, 150),
+                (35..40, 120),
+                (40..135, 50),  // only map2
+                (135..200, 20), // only map2
+            ]
+        );
+    }
+
+    #[test]
+    fn not_really_a_dead_end() {
+        let map1 = map(vec![(10..50, 10)]);
+        let map2 = map(vec![(0..5, 20)]);
+        let result = map1.then(&map2);
+        assert_eq!(result.pieces(), [(0..5, 20), (10..50, 10)]);
+    }
+
+    #[test]
+    fn overlapping_ranges() {
+        let input = "seeds: 1 2
+
+seed-to-location map:
+30 20 15
+50 30 5";
+        let err = Almanac::new(input).err().expect("overlap rejected");
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: overlapping source ranges in map"
+        );
+    }
+
+    #[test]
+    fn range_end_too_large() {
+        let input = "seeds: 1 2
+
+seed-to-location map:
+50 98 18446744073709551615";
+        let err = Almanac::new(input).err().expect("overflow rejected");
+        assert_eq!(err.to_string(), "line 4, column 1: range end too large");
+    }
+
+    #[test]
+    fn invalid_seed_ranges() {
+        let maps = "
+
+seed-to-location map:
+30 20 15";
+        let odd = Almanac::new(&format!("seeds: 79 14 55{maps}")).unwrap();
+        assert_eq!(odd.closest_seed_location().unwrap(), 14);
+        let err = odd.closest_seed_range_location().unwrap_err();
+        assert_eq!(err.to_string(), "seed 55 has no range length");
+
+        let overflow = Almanac::new(&format!("seeds: 18446744073709551615 14{maps}")).unwrap();
+        let err = overflow.closest_seed_range_location().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "seed range 18446744073709551615 of length 14 ends past 18446744073709551615"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{log_debug, log_trace, ParseError};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 6;
+    type Parsed = Races;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        let normal = parse_race_info(input, Kerning::Normal)?;
+        Ok(Races {
+            normal,
+            bad_kerning: parse_race_info(input, Kerning::Bad),
+        })
+    }
+    fn part1(races: &Self::Parsed) -> anyhow::Result<u128> {
+        product_of_win_options(&races.normal)
+    }
+    fn part2(races: &Self::Parsed) -> anyhow::Result<u128> {
+        let races = races.bad_kerning.as_ref().map_err(Clone::clone)?;
+        product_of_win_options(races)
+    }
+}
+
+/// Races of the input, read with each kerning
+pub struct Races {
+    normal: Vec<RaceInfo>,
+    /// Joining the digits may overflow where each number fits, which only fails part 2
+    bad_kerning: Result<Vec<RaceInfo>, ParseError>,
+}
+
+/// <https://en.wikipedia.org/wiki/Kerning>
+#[derive(Clone, Copy, Debug)]
+enum Kerning {
+    /// Normal, spaces mean spaces
+    Normal,
+    /// You realize the piece of paper just has very bad kerning...
+    Bad,
+}
+
+#[cfg(test)]
+fn races_stats(input: &str, kerning: Kerning) -> anyhow::Result<u128> {
+    let races = parse_race_info(input, kerning)?;
+    product_of_win_options(&races)
+}
+
+fn product_of_win_options(races: &[RaceInfo]) -> anyhow::Result<u128> {
+    log_debug!("{races:?}");
+    races
+        .iter()
+        .copied()
+        .map(RaceInfo::count_win_options)
+        .try_fold(1_u128, |product, count| {
+            product
+                .checked_mul(count)
+                .ok_or_else(|| anyhow::anyhow!("product of win options overflows u128"))
+        })
+}
+
+fn parse_race_info(input: &str, kerning: Kerning) -> Result<Vec<RaceInfo>, ParseError> {
+    let mut lines = input.lines();
+
+    let Some(line_time) = lines.next() else {
+        return Err(ParseError::new("expected first line for time"));
+    };
+    let Some(line_distance) = lines.next() else {
+        return Err(ParseError::new("expected second line for distance"));
+    };
+
+    match lines.next() {
+        Some(extra) if !extra.is_empty() => {
+            return Err(ParseError::new("extra line").at(2, extra, extra));
+        }
+        _ => {}
+    }
+
+    let times = parse_numbers(0, line_time, "Time:", kerning)?;
+    let distances = parse_numbers(1, line_distance, "Distance:", kerning)?;
+
+    let mut times = times.into_iter();
+    let mut distances = distances.into_iter();
+
+    let mut races = vec![];
+
+    loop {
+        match (times.next(), distances.next()) {
+            (None, None) => break,
+            (Some((_, text)), None) => {
+                return Err(ParseError::new("no distance present for time").at(0, line_time, text));
+            }
+            (None, Some((_, text))) => {
+                return Err(ParseError::new("no time present for distance").at(
+                    1,
+                    line_distance,
+                    text,
+                ));
+            }
+            (Some((duration, _)), Some((min_distance, _))) => {
+                races.push(RaceInfo {
+                    duration,
+                    min_distance,
+                });
+            }
+        }
+    }
+
+    Ok(races)
+}
+
+/// Returns the numbers after the label, each with its text within the line
+///
+/// With bad kerning, all digits after the label are one number.
+fn parse_numbers<'a>(
+    line_index: usize,
+    line: &'a str,
+    label: &str,
+    kerning: Kerning,
+) -> Result<Vec<(u128, &'a str)>, ParseError> {
+    let Some(numbers) = line.strip_prefix(label) else {
+        return Err(ParseError::new(format!("expected label {label:?}")).at(line_index, line, line));
+    };
+    let parse_int = |digits: &str, text| {
+        digits.parse::<u128>().map_err(|err| {
+            ParseError::new(format!("invalid number: {err}")).at(line_index, line, text)
+        })
+    };
+    match kerning {
+        Kerning::Normal => numbers
+            .split_whitespace()
+            .map(|text| Ok((parse_int(text, text)?, text)))
+            .collect(),
+        Kerning::Bad => {
+            let text = numbers.trim();
+            if text.is_empty() {
+                return Ok(vec![]);
+            }
+            let digits = text.replace(char::is_whitespace, "");
+            Ok(vec![(parse_int(&digits, text)?, text)])
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RaceInfo {
+    duration: u128,
+    min_distance: u128,
+}
+
+impl RaceInfo {
+    /// The distance traveled in a race is the area of a rectangle between (0, 0) and (x, y),
+    /// for the curve `y = duration - x`
+    ///
+    /// ```text
+    /// \
+    ///  \  -  Line defined by 7 second duration  (y = 7 - x)
+    ///   \
+    ///    \
+    /// ____O  - Hold button for 4 seconds, travels for 3 seconds
+    /// ****|\
+    /// *12*| \
+    /// ****|  \
+    /// ```
+    ///
+    fn count_win_options(self) -> u128 {
+        let Self {
+            duration,
+            min_distance,
+        } = self;
+        let success_count = crate::math::count_products_exceeding(duration, min_distance);
+        log_trace!("{self:?} success_count={success_count}");
+        success_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day6_boat_races::{parse_race_info, races_stats, Kerning, RaceInfo};
+
+    macro_rules! tests {
+        (
+            $(
+                [$duration:expr, $min_distance:expr] => $expected:expr;
+            )+
+        ) => {
+            $({
+                let duration = $duration;
+                let min_distance = $min_distance;
+                let expected = $expected;
+                assert_eq!(
+                    RaceInfo {
+                        duration,
+                        min_distance,
+                    }
+                    .count_win_options(),
+                    expected,
+                    "[{duration} {min_distance}] => {expected}"
+                );
+            })+
+        };
+    }
+
+    #[test]
+    fn race_impossible() {
+        tests! {
+            [7, 4*4] => 0;
+            [8, 4*4] => 0;
+            [9, 4*5] => 0;
+        };
+    }
+    #[test]
+    fn race_one() {
+        tests! {
+            [6, 8] => 1;
+        };
+    }
+    #[test]
+    fn race_two() {
+        tests! {
+            [5, 5] => 2;
+        };
+    }
+    #[test]
+    fn race_four() {
+        tests! {
+            [7, 9] => 4;
+            [9, 4*4] => 4;
+        };
+    }
+
+    #[test]
+    fn sample_races() {
+        tests! {
+            [7, 9] => 4;
+            [15, 40] => 8;
+            [30, 200] => 9;
+        }
+    }
+
+    #[test]
+    fn sample_input() {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        let result = races_stats(input, Kerning::Normal).expect("valid input");
+        assert_eq!(result, 4 * 8 * 9);
+    }
+
+    #[test]
+    fn sample_input_kerned() {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        let result = races_stats(input, Kerning::Bad).expect("valid input");
+        assert_eq!(result, 71503);
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let input = "Time:      7  15   30\nDistance:  9  4O  200";
+        let err = parse_race_info(input, Kerning::Normal).unwrap_err();
+        let location = err.location().expect("located");
+        assert_eq!((location.line, location.column), (2, 15));
+        assert_eq!(location.text, "4O");
+
+        let input = "Time:      7  15   30\nDistance:  9  40";
+        let err = parse_race_info(input, Kerning::Normal).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 20: no distance present for time"
+        );
+        assert!(parse_race_info("garbage\nDistance: 9", Kerning::Bad).is_err());
+    }
+
+    #[test]
+    fn product_overflow() {
+        let input = format!("Time: {0} {0}\nDistance: 0 0", u128::MAX);
+        let err = races_stats(&input, Kerning::Normal).unwrap_err();
+        assert_eq!(err.to_string(), "product of win options overflows u128");
+    }
+}
@@ -1,19 +1,32 @@
 use std::{cmp::Reverse, collections::BTreeMap};
 
-fn main() -> anyhow::Result<()> {
-    println!("hello, camel cards!");
-
-    let input = advent_2023::get_input_string()?;
-
-    let total_winnings = analyze_hands(&input, Rules::Jokers)?;
-    println!("Total winnings for all hands: {total_winnings}");
-
-    Ok(())
+use crate::{
+    log::{self, Level},
+    log_trace,
+    parse_error::parse_chars,
+    ParseError,
+};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 7;
+    type Parsed = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(input.to_owned())
+    }
+    fn part1(input: &Self::Parsed) -> anyhow::Result<u32> {
+        analyze_hands(input, Rules::Jacks)
+    }
+    fn part2(input: &Self::Parsed) -> anyhow::Result<u32> {
+        analyze_hands(input, Rules::Jokers)
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 enum Rules {
-    #[allow(unused)]
     Jacks,
     Jokers,
 }
@@ -21,46 +34,48 @@ enum Rules {
 fn analyze_hands(input: &str, rules: Rules) -> anyhow::Result<u32> {
     let mut hands = input
         .lines()
-        .map(|line| parse_hand(line, rules))
+        .enumerate()
+        .map(|(line_index, line)| parse_hand(line_index, line, rules))
         .collect::<Result<Vec<_>, _>>()?;
 
     // // DEBUG
-    // println!("Input Hands:");
+    // eprintln!("Input Hands:");
     // for (hand, Bid(bid)) in &hands {
     //     let Hand { cards, ty } = hand;
-    //     println!("\tHand {cards:?} {ty:?}, bid {bid}");
+    //     eprintln!("\tHand {cards:?} {ty:?}, bid {bid}");
     // }
 
     hands.sort_by_key(|&(hand, _bid)| hand);
 
-    // DEBUG
-    println!("Sorted Hands:");
-    for (index, (hand, Bid(bid))) in hands.iter().enumerate() {
-        let Hand { cards, ty } = hand;
-        // println!("\tHand {cards:?} {ty:?}, bid {bid}");
-
-        // DEBUG, for comparison
-        let ty_number = match ty {
-            Type::HighCard => 1,
-            Type::OnePair => 2,
-            Type::TwoPair => 3,
-            Type::ThreeOfAKind => 4,
-            Type::FullHouse => 5,
-            Type::FourOfAKind => 6,
-            Type::FiveOfAKind => 7,
-        };
-        let cards_str = cards.iter().fold(String::new(), |mut acc, c| {
-            if *c == Card::Joker {
-                acc += "J";
-            } else {
-                acc += &format!("{c}");
-            }
-            acc
-        });
-        println!(
-            "{count:3}. {cards_str} ({bid:3}) {ty_number}",
-            count = index + 1,
-        );
+    if log::enabled(Level::Debug) {
+        eprintln!("Sorted Hands:");
+        for (index, (hand, Bid(bid))) in hands.iter().enumerate() {
+            let Hand { cards, ty } = hand;
+            // eprintln!("\tHand {cards:?} {ty:?}, bid {bid}");
+
+            // DEBUG, for comparison
+            let ty_number = match ty {
+                Type::HighCard => 1,
+                Type::OnePair => 2,
+                Type::TwoPair => 3,
+                Type::ThreeOfAKind => 4,
+                Type::FullHouse => 5,
+                Type::FourOfAKind => 6,
+                Type::FiveOfAKind => 7,
+            };
+            let cards_str = cards.iter().fold(String::new(), |mut acc, c| {
+                if *c == Card::Joker {
+                    acc += "J";
+                } else {
+                    acc += &format!("{c}");
+                }
+                acc
+            });
+            eprintln!(
+                "{count:3}. {cards_str} ({bid:3}) {ty_number}",
+                count = index + 1,
+            );
+        }
     }
 
     let total_winnings = hands
@@ -73,20 +88,24 @@ fn analyze_hands(input: &str, rules: Rules) -> anyhow::Result<u32> {
         .sum();
     Ok(total_winnings)
 }
-fn parse_hand(line: &str, rules: Rules) -> anyhow::Result<(Hand, Bid)> {
+fn parse_hand(line_index: usize, line: &str, rules: Rules) -> Result<(Hand, Bid), ParseError> {
     let mut line_parts = line.split_whitespace();
     let Some(hand) = line_parts.next() else {
-        anyhow::bail!("empty line")
+        return Err(ParseError::new("empty line").at(line_index, line, line));
     };
     let Some(bid) = line_parts.next() else {
-        anyhow::bail!("missing bid on line {line:?}")
+        return Err(ParseError::new("missing bid").at(line_index, line, &line[line.len()..]));
     };
     if let Some(extra) = line_parts.next() {
-        anyhow::bail!("unexpected part on line {line:?}: {extra:?}")
+        return Err(ParseError::new("unexpected part after bid").at(line_index, line, extra));
     }
 
-    let hand: Hand = hand.parse().map_err(|s| anyhow::anyhow!("hand {s:?}"))?;
-    let bid = bid.parse().map_err(|s| anyhow::anyhow!("bid {s:?}"))?;
+    let hand: Hand = hand
+        .parse()
+        .map_err(|err: ParseError| err.at(line_index, line, hand))?;
+    let bid = bid
+        .parse()
+        .map_err(|err| ParseError::new(format!("invalid bid: {err}")).at(line_index, line, bid))?;
 
     let debug_original_ty = hand.ty;
 
@@ -94,7 +113,7 @@ fn parse_hand(line: &str, rules: Rules) -> anyhow::Result<(Hand, Bid)> {
         Rules::Jacks => hand,
         Rules::Jokers => hand.reinterpret_as_jokers(),
     };
-    if hand.ty != debug_original_ty {
+    if hand.ty != debug_original_ty && log::enabled(Level::Trace) {
         let cards_str = hand
             .cards
             .iter()
@@ -145,14 +164,13 @@ impl Ord for Hand {
     }
 }
 impl std::str::FromStr for Hand {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cards = s
-            .chars()
-            .map(Card::try_from)
-            .collect::<Result<Vec<Card>, _>>()?
+        let cards = parse_chars::<Card>(s)?
             .try_into()
-            .map_err(|cards_vec| format!("invalid number of cards, {cards_vec:?}"))?;
+            .map_err(|cards_vec: Vec<Card>| {
+                ParseError::new(format!("expected 5 cards, found {}", cards_vec.len()))
+            })?;
         let ty = Type::from(&cards);
         Ok(Self { cards, ty })
     }
@@ -184,7 +202,7 @@ impl From<&[Card; 5]> for Type {
         let count_2_no_jokers = counts.iter().filter(|(_, &count)| count == 2).count();
 
         let count_wild = counts.get(&Card::Joker).copied().unwrap_or_default();
-        dbg!(cards, &counts, count_wild);
+        log_trace!("{cards:?} counts={counts:?} count_wild={count_wild}");
         let counts_wild = {
             let mut counts_wild = counts.clone();
 
@@ -217,7 +235,7 @@ impl From<&[Card; 5]> for Type {
             Self::FourOfAKind
         } else {
             let mut allow_full_house = count_3_no_jokers >= 1 && count_2_no_jokers >= 1;
-            dbg!(allow_full_house, count_wild, count_3, count_2);
+            log_trace!("allow_full_house={allow_full_house} count_wild={count_wild} count_3={count_3} count_2={count_2}");
             if !allow_full_house && count_wild >= 1 && count_3 >= 1 && count_2 >= 1 {
                 let mut count_wild_remaining = count_wild;
                 let mut counts_added: Vec<_> = counts
@@ -230,7 +248,7 @@ impl From<&[Card; 5]> for Type {
                 let mut counts_added = counts_added.into_iter();
                 let count_first = counts_added.next();
                 let count_second = counts_added.next();
-                dbg!(count_first, count_second);
+                log_trace!("count_first={count_first:?} count_second={count_second:?}");
                 if let Some((mut count_first, mut count_second)) = count_first.zip(count_second) {
                     // threes
                     while count_first < 3 && count_wild_remaining > 0 {
@@ -241,7 +259,7 @@ impl From<&[Card; 5]> for Type {
                         count_wild_remaining -= 1;
                         count_second += 1;
                     }
-                    dbg!(&counts, count_wild, count_first, count_second);
+                    log_trace!("counts={counts:?} count_wild={count_wild} count_first={count_first} count_second={count_second}");
                     // check again
                     if count_first >= 3 && count_second >= 2 {
                         allow_full_house = true;
@@ -294,7 +312,7 @@ impl Card {
 }
 
 impl TryFrom<char> for Card {
-    type Error = char;
+    type Error = ParseError;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         let value = match value {
             '2' => Self::N2,
@@ -311,7 +329,7 @@ impl TryFrom<char> for Card {
             'K' => Self::K,
             'A' => Self::A,
             _ => {
-                return Err(value);
+                return Err(ParseError::unexpected_char(value));
             }
         };
         Ok(value)
@@ -346,7 +364,10 @@ impl std::fmt::Debug for Card {
 
 #[cfg(test)]
 mod tests {
-    use crate::{analyze_hands, parse_hand, Card, Rules, Type};
+    use crate::{
+        days::day7_camel_cards::{analyze_hands, parse_hand, Card, Rules, Type},
+        ParseError,
+    };
 
     macro_rules! test_types {
         (
@@ -358,7 +379,7 @@ mod tests {
             let rules: Rules = $rules;
             $({
                 let hand_str: &'static str = $str;
-                let (hand, _) = parse_hand(&format!("{hand_str} 0"), rules).expect(hand_str);
+                let (hand, _) = parse_hand(0, &format!("{hand_str} 0"), rules).expect(hand_str);
                 assert_eq!(hand.ty, $expected_ty, "hand {hand_str} {hand:?}");
                 println!("------------------------------");
             })+
@@ -449,4 +470,17 @@ QQQJA 483";
             "QQ5J5" => Type::FullHouse;
         }
     }
+    #[test]
+    fn parse_error_location() {
+        let input = "32T3K 765\nT55X5 684";
+        let err = analyze_hands(input, Rules::Jacks).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("parse error");
+        let location = err.location().expect("located");
+        assert_eq!((location.line, location.column), (2, 4));
+        assert_eq!(location.text, "X");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: unexpected character 'X'"
+        );
+    }
 }
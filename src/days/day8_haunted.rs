@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cycle::{self, Cycle},
+    log_debug, math,
+    parse_error::parse_chars,
+    ParseError,
+};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 8;
+    type Parsed = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse_input(input)
+    }
+    fn part1(parsed: &Parsed) -> anyhow::Result<usize> {
+        let start = "AAA".parse().expect("valid start");
+        let end = "ZZZ".parse().expect("valid end");
+        parsed.find_shortest_count(start, end)
+    }
+    fn part2(parsed: &Parsed) -> anyhow::Result<usize> {
+        parsed.find_shortest_parallel('A', 'Z')
+    }
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Parsed> {
+    let mut lines = input.lines();
+
+    let Some(instructions_line) = lines.next() else {
+        anyhow::bail!("missing instructions line")
+    };
+
+    let Some(blank_line) = lines.next() else {
+        anyhow::bail!("missing blank separator line")
+    };
+    if !blank_line.is_empty() {
+        anyhow::bail!(ParseError::new("blank line is not blank").at(1, blank_line, blank_line));
+    }
+
+    let instructions = parse_chars(instructions_line)
+        .map_err(|err| err.at(0, instructions_line, instructions_line))?;
+
+    let maps = lines
+        .enumerate()
+        .map(|(index, line)| parse_map_line(index + 2, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Parsed { instructions, maps })
+}
+
+fn parse_map_line(line_index: usize, line: &str) -> Result<(Key, (Key, Key)), ParseError> {
+    let Some((key, values)) = line.split_once(" = (") else {
+        return Err(
+            ParseError::new("missing key/value delimiter \" = (\"").at(line_index, line, line)
+        );
+    };
+    let Some((value1, value2)) = values.split_once(", ") else {
+        return Err(
+            ParseError::new("missing comma delimiter between values").at(line_index, line, values)
+        );
+    };
+    let value2 = value2.strip_suffix(')').unwrap_or(value2);
+
+    let parse_key = |text: &str| {
+        text.parse::<Key>()
+            .map_err(|err| err.at(line_index, line, text))
+    };
+    Ok((parse_key(key)?, (parse_key(value1)?, parse_key(value2)?)))
+}
+
+#[derive(Debug)]
+pub struct Parsed {
+    instructions: Vec<Instruction>,
+    maps: BTreeMap<Key, (Key, Key)>,
+}
+impl Parsed {
+    fn find_shortest_count(&self, start: Key, end: Key) -> anyhow::Result<usize> {
+        self.find_count(start, |current| current == end)
+    }
+    fn find_count(&self, start: Key, accept_fn: impl Fn(Key) -> bool) -> anyhow::Result<usize> {
+        let mut instructions = std::iter::repeat(self.instructions.iter()).flatten();
+
+        let mut current = start;
+        let mut count = 0;
+        while !(accept_fn)(current) {
+            let Some((value1, value2)) = self.maps.get(&current).copied() else {
+                anyhow::bail!("mapping not found for current {current:?}")
+            };
+
+            let Some(instruction) = instructions.next() else {
+                anyhow::bail!("instructions iter empty")
+            };
+
+            current = instruction.choose(value1, value2);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns the first step where all ghosts (one per start key) are on end keys
+    ///
+    /// Each ghost's position repeats with a period, possibly after some initial steps, so the
+    /// alignment is found by combining the cycles rather than stepping all ghosts together.
+    fn find_shortest_parallel(&self, start_third: char, end_third: char) -> anyhow::Result<usize> {
+        if self.instructions.is_empty() {
+            anyhow::bail!("no instructions to follow")
+        }
+        if let Some(missing) = self
+            .maps
+            .values()
+            .flat_map(|&(value1, value2)| [value1, value2])
+            .find(|value| !self.maps.contains_key(value))
+        {
+            anyhow::bail!("mapping not found for {missing:?}")
+        }
+
+        let ghosts: Vec<GhostCycle> = self
+            .maps
+            .keys()
+            .filter(|key| key.ends_with(start_third))
+            .map(|&key| {
+                let initial = Ghost {
+                    key,
+                    instruction_index: 0,
+                };
+                let (cycle, states) = cycle::find_cycle(initial, |ghost| self.step_ghost(*ghost));
+                let end_steps = states
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, ghost)| ghost.key.ends_with(end_third))
+                    .map(|(step, _)| step)
+                    .collect();
+                GhostCycle { cycle, end_steps }
+            })
+            .collect();
+        if ghosts.is_empty() {
+            anyhow::bail!("no start keys ending in {start_third:?}")
+        }
+
+        // before all ghosts are cycling, check each step directly
+        let settled = ghosts
+            .iter()
+            .map(|ghost| ghost.cycle.start)
+            .max()
+            .unwrap_or_default();
+        if let Some(step) = (0..settled).find(|&step| ghosts.iter().all(|ghost| ghost.is_end(step)))
+        {
+            return Ok(step);
+        }
+
+        // afterward, each choice of in-cycle end step per ghost is a set of congruences
+        let mut alignments = vec![(0, 1)];
+        for ghost in &ghosts {
+            let period = ghost.cycle.length as u64;
+            alignments = alignments
+                .iter()
+                .flat_map(|&alignment| {
+                    ghost
+                        .end_steps
+                        .iter()
+                        .filter(|&&step| step >= ghost.cycle.start)
+                        .filter_map(move |&step| {
+                            math::crt([alignment, (step as u64 % period, period)])
+                        })
+                })
+                .collect();
+        }
+        log_debug!("cycles {ghosts:?}, settled after {settled}, alignments {alignments:?}");
+
+        let settled = settled as u64;
+        let Some(first) = alignments
+            .into_iter()
+            .map(|(offset, period)| {
+                // first step at or after settling, with the offset
+                offset + settled.saturating_sub(offset).div_ceil(period) * period
+            })
+            .min()
+        else {
+            anyhow::bail!("ghosts never align on end keys")
+        };
+        Ok(usize::try_from(first)?)
+    }
+    fn step_ghost(&self, ghost: Ghost) -> Ghost {
+        let Ghost {
+            key,
+            instruction_index,
+        } = ghost;
+        let (value1, value2) = self.maps[&key];
+        let instruction = self.instructions[instruction_index];
+        Ghost {
+            key: instruction.choose(value1, value2),
+            instruction_index: (instruction_index + 1) % self.instructions.len(),
+        }
+    }
+}
+
+/// Position of a ghost, before following the instruction at the index
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Ghost {
+    key: Key,
+    instruction_index: usize,
+}
+
+#[derive(Debug)]
+struct GhostCycle {
+    cycle: Cycle,
+    /// Steps before the cycle repeats where the ghost is on an end key
+    end_steps: Vec<usize>,
+}
+impl GhostCycle {
+    fn is_end(&self, step: usize) -> bool {
+        self.end_steps
+            .binary_search(&self.cycle.reduce(step))
+            .is_ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Key([char; 3]);
+impl Key {
+    fn ends_with(self, expected: char) -> bool {
+        let Self([_, _, end]) = self;
+        end == expected
+    }
+}
+impl std::str::FromStr for Key {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let c1 = chars.next();
+        let c2 = chars.next();
+        let c3 = chars.next();
+        let extra = chars.next();
+        if let Some(((c1, c2), c3)) = c1.zip(c2).zip(c3) {
+            if extra.is_none() {
+                return Ok(Key([c1, c2, c3]));
+            }
+        }
+        Err(ParseError::new(format!(
+            "expected key of 3 characters, found {s:?}"
+        )))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Instruction {
+    Left,
+    Right,
+}
+impl Instruction {
+    fn choose<T>(self, left: T, right: T) -> T {
+        match self {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        }
+    }
+}
+impl TryFrom<char> for Instruction {
+    type Error = ParseError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            _ => Err(ParseError::unexpected_char(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day8_haunted::{parse_input, Instruction, Key};
+    use crate::search;
+
+    #[test]
+    fn sample_input_parse() {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(
+            parsed.instructions,
+            vec![Instruction::Left, Instruction::Left, Instruction::Right]
+        );
+
+        let aaa = "AAA".parse().unwrap();
+        let bbb = "BBB".parse().unwrap();
+        let zzz = "ZZZ".parse().unwrap();
+        assert_eq!(parsed.maps.get(&aaa), Some(&(bbb, bbb)));
+        assert_eq!(parsed.maps.get(&bbb), Some(&(aaa, zzz)));
+        assert_eq!(parsed.maps.get(&zzz), Some(&(zzz, zzz)));
+
+        let shortest = parsed.find_shortest_count(aaa, zzz).unwrap();
+        assert_eq!(shortest, 6);
+    }
+
+    #[test]
+    fn sample_input_moderate() {
+        let input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(
+            parsed.instructions,
+            vec![Instruction::Right, Instruction::Left]
+        );
+
+        let aaa = "AAA".parse().unwrap();
+        let bbb = "BBB".parse().unwrap();
+        let ccc = "CCC".parse().unwrap();
+        let ddd = "DDD".parse().unwrap();
+        let eee = "EEE".parse().unwrap();
+        let ggg = "GGG".parse().unwrap();
+        let zzz = "ZZZ".parse().unwrap();
+        assert_eq!(parsed.maps.get(&aaa), Some(&(bbb, ccc)));
+        assert_eq!(parsed.maps.get(&bbb), Some(&(ddd, eee)));
+        assert_eq!(parsed.maps.get(&ccc), Some(&(zzz, ggg)));
+        assert_eq!(parsed.maps.get(&ddd), Some(&(ddd, ddd)));
+        assert_eq!(parsed.maps.get(&eee), Some(&(eee, eee)));
+        assert_eq!(parsed.maps.get(&ggg), Some(&(ggg, ggg)));
+        assert_eq!(parsed.maps.get(&zzz), Some(&(zzz, zzz)));
+
+        let shortest = parsed.find_shortest_count(aaa, zzz).unwrap();
+        assert_eq!(shortest, 2);
+    }
+
+    #[test]
+    fn sample_input_parallel() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+        let parsed = parse_input(input).unwrap();
+        let length = parsed.find_shortest_parallel('A', 'Z').unwrap();
+        assert_eq!(length, 6);
+    }
+
+    #[test]
+    fn key_graph_search() {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let parsed = parse_input(input).unwrap();
+        let aaa: Key = "AAA".parse().unwrap();
+        let bbb: Key = "BBB".parse().unwrap();
+        let zzz: Key = "ZZZ".parse().unwrap();
+
+        // ignoring the instructions, either branch may be taken
+        let explored = search::bfs(aaa, |key| {
+            let (value1, value2) = parsed.maps[key];
+            [value1, value2]
+        });
+        assert_eq!(explored.distance(&zzz), Some(2));
+        assert_eq!(explored.path_to(&zzz), Some(vec![aaa, bbb, zzz]));
+    }
+
+    #[test]
+    fn parallel_with_offsets() {
+        // 11A reaches 11Z at steps 2, 4, 6, ...; 22A reaches 22Z at steps 1, 4, 7, ...
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11Z, 11Z)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)";
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(parsed.find_shortest_parallel('A', 'Z').unwrap(), 4);
+
+        assert_eq!(parsed.find_shortest_parallel('B', 'Z').unwrap(), 5);
+
+        // aligned only before 44A settles into a cycle away from 44Z
+        let input = "L
+
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+44A = (44Z, 44Z)
+44Z = (44B, 44B)
+44B = (44B, 44B)";
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(parsed.find_shortest_parallel('A', 'Z').unwrap(), 1);
+
+        // 11A is on 11Z only at odd steps, 33A on 33Z only at even steps
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+33A = (33B, 33B)
+33B = (33Z, 33Z)
+33Z = (33B, 33B)";
+        let parsed = parse_input(input).unwrap();
+        assert!(parsed.find_shortest_parallel('A', 'Z').is_err());
+    }
+}
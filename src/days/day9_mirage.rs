@@ -0,0 +1,107 @@
+use crate::{sequence::DifferenceTable, ParseError};
+
+pub struct Solution;
+impl crate::Solver for Solution {
+    const DAY: u8 = 9;
+    type Parsed = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse_input(input)?)
+    }
+    fn part1(inputs: &Self::Parsed) -> anyhow::Result<i64> {
+        sum_next_values(inputs)
+    }
+    fn part2(inputs: &Self::Parsed) -> anyhow::Result<i64> {
+        sum_previous_values(inputs)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| {
+            let series = line
+                .split_whitespace()
+                .map(|s| {
+                    s.parse().map_err(|e| {
+                        ParseError::new(format!("invalid number: {e}")).at(line_index, line, s)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if series.is_empty() {
+                return Err(ParseError::new("empty series").at(line_index, line, line));
+            }
+            Ok(series)
+        })
+        .collect()
+}
+
+fn sum_next_values(inputs: &[Vec<i64>]) -> anyhow::Result<i64> {
+    sum_predictions(inputs, DifferenceTable::next_value)
+}
+fn sum_previous_values(inputs: &[Vec<i64>]) -> anyhow::Result<i64> {
+    sum_predictions(inputs, DifferenceTable::previous_value)
+}
+
+fn sum_predictions(
+    inputs: &[Vec<i64>],
+    predict_fn: impl Fn(&DifferenceTable) -> Option<i64>,
+) -> anyhow::Result<i64> {
+    inputs
+        .iter()
+        .map(|series| {
+            // series are nonempty from parsing, so only the differences can fail
+            let Some(table) = DifferenceTable::new(series) else {
+                anyhow::bail!("differences overflow for {series:?}")
+            };
+            predict_fn(&table).ok_or_else(|| anyhow::anyhow!("prediction overflow for {series:?}"))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day9_mirage::{parse_input, sum_next_values, sum_previous_values};
+
+    #[test]
+    fn sample_input() {
+        let input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+        let inputs = parse_input(input).unwrap();
+        dbg!(&inputs);
+
+        let sum_nexts = sum_next_values(&inputs).unwrap();
+        assert_eq!(sum_nexts, 114);
+    }
+    #[test]
+    fn sample_reverse() {
+        let input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+        let inputs = parse_input(input).unwrap();
+        dbg!(&inputs);
+
+        let sum_prevs = sum_previous_values(&inputs).unwrap();
+        assert_eq!(sum_prevs, 2);
+    }
+    #[test]
+    fn parse_error_location() {
+        let err = parse_input("0 3 6\n1 3 x 10").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: invalid number: invalid digit found in string"
+        );
+        let err = parse_input("0 3 6\n\n1 2").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: empty series");
+    }
+
+    #[test]
+    fn differences_overflow() {
+        let inputs = parse_input(&format!("{} {}", i64::MIN, i64::MAX)).unwrap();
+        assert!(sum_next_values(&inputs).is_err());
+    }
+}
@@ -0,0 +1,744 @@
+//! Random puzzle inputs, for stress-testing and benchmarking the solvers at scale
+//!
+//! Each input is built to be valid for its day (e.g. the pipe maze always has a single loop through
+//! the start), and the same day, size and seed always give the same input.
+
+use std::fmt::Write;
+
+/// Generates an input for the day, with `size` scaling the line count or the grid dimensions
+pub fn generate(day: u8, size: usize, seed: u64) -> anyhow::Result<String> {
+    let generate_fn: fn(&mut Rng, usize) -> String = match day {
+        1 => trebuchet,
+        2 => cube_conundrum,
+        3 => gear_ratios,
+        4 => scratchcards,
+        5 => seed_locations,
+        6 => boat_races,
+        7 => camel_cards,
+        8 => haunted,
+        9 => mirage,
+        10 => pipe_maze,
+        11 => cosmic,
+        12 => springs,
+        13 => mirrors,
+        14 => rocks_roll,
+        15 => lens_library,
+        16 => mirror_beams,
+        _ => anyhow::bail!("no generator for day {day}"),
+    };
+    let size = size.max(1);
+    let mut rng = Rng::new(seed);
+    Ok(generate_fn(&mut rng, size))
+}
+
+/// Small deterministic random number generator (SplitMix64)
+///
+/// Kept in-tree so generated inputs stay the same across dependency updates.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Returns a value in `0..bound`
+    ///
+    /// Panics if `bound` is zero
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert_ne!(bound, 0, "empty range");
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+    /// Returns a value in the range
+    ///
+    /// Panics if the range is empty
+    pub fn range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        range.start + self.below(range.end - range.start)
+    }
+    /// Returns an index for a slice of the specified length
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+    /// Returns `true` with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+    /// Returns a random element
+    ///
+    /// Panics if the slice is empty
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.index(items.len())]
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for end in (1..items.len()).rev() {
+            items.swap(end, self.index(end + 1));
+        }
+    }
+}
+
+/// Returns a grid of characters chosen by the function, one line per row
+fn grid_lines(
+    rng: &mut Rng,
+    (width, height): (usize, usize),
+    mut cell_fn: impl FnMut(&mut Rng) -> char,
+) -> String {
+    let mut output = String::new();
+    for _ in 0..height {
+        output.extend((0..width).map(|_| cell_fn(rng)));
+        output.push('\n');
+    }
+    output
+}
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn trebuchet(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        // at least one digit character, for part 1
+        let digit_position = rng.range(0..6);
+        for position in 0..6 {
+            if position == digit_position {
+                line.push(char::from(b'1' + rng.range(0..9) as u8));
+            }
+            match rng.below(3) {
+                0 => line.push_str(rng.choose(&DIGIT_NAMES)),
+                _ => line.extend(
+                    (0..rng.range(1..4)).map(|_| rng.choose(b"abcdefghijklmnopqrstuvwxyz") as char),
+                ),
+            }
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+fn cube_conundrum(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for game_id in 1..=size {
+        let draws: Vec<String> = (0..rng.range(1..7))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let count = rng.range(1..4) as usize;
+                colors[..count]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.range(1..21)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        writeln!(output, "Game {game_id}: {}", draws.join("; ")).expect("infallible");
+    }
+    output
+}
+
+/// Schematic with numbers of at most 3 digits
+fn gear_ratios(rng: &mut Rng, size: usize) -> String {
+    let mut digits = 0;
+    let mut column = 0;
+    grid_lines(rng, (size, size), |rng| {
+        if column == 0 {
+            digits = 0;
+        }
+        column = (column + 1) % size;
+        let cell = match rng.below(12) {
+            0..=5 => '.',
+            6..=9 => char::from(b'0' + rng.below(10) as u8),
+            10 => '*',
+            _ => rng.choose(&['#', '$', '+', '/', '@', '=', '%', '&', '-']),
+        };
+        digits = if cell.is_ascii_digit() { digits + 1 } else { 0 };
+        if digits > 3 {
+            digits = 0;
+            '.'
+        } else {
+            cell
+        }
+    })
+}
+
+/// Cards with few matches each, like the puzzle's, so the won copies stay countable
+///
+/// Copies are tracked while generating: a card already held many times wins nothing, and no card
+/// wins copies past the last card.
+fn scratchcards(rng: &mut Rng, size: usize) -> String {
+    const WINNING_COUNT: usize = 10;
+    const HAVE_COUNT: usize = 25;
+    const MAX_COPIES: usize = 1000;
+    let number_width = size.to_string().len();
+    let mut copies = vec![1_usize; size];
+    let mut output = String::new();
+    for card_index in 0..size {
+        let remaining = size - card_index - 1;
+        // favor fewer matches, as a drawn bound then a count below it
+        let matching = if copies[card_index] > MAX_COPIES {
+            0
+        } else {
+            let bound = rng.index(WINNING_COUNT + 1);
+            rng.index(bound + 1).min(remaining)
+        };
+        for won in (card_index + 1)..=(card_index + matching) {
+            copies[won] += copies[card_index];
+        }
+
+        let mut numbers: Vec<u64> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, others) = numbers.split_at(WINNING_COUNT);
+        let mut have: Vec<u64> = winning[..matching]
+            .iter()
+            .chain(&others[..(HAVE_COUNT - matching)])
+            .copied()
+            .collect();
+        rng.shuffle(&mut have);
+        let format_numbers = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|n| format!("{n:2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(
+            output,
+            "Card {:number_width$}: {} | {}",
+            card_index + 1,
+            format_numbers(winning),
+            format_numbers(&have)
+        )
+        .expect("infallible");
+    }
+    output
+}
+
+fn seed_locations(rng: &mut Rng, size: usize) -> String {
+    const KINDS: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    const DOMAIN: u64 = 1 << 32;
+
+    let seeds: Vec<String> = (0..size.min(10))
+        .map(|_| {
+            let start = rng.below(DOMAIN);
+            let len = rng.range(1..(DOMAIN - start).min(1 << 28) + 1);
+            format!("{start} {len}")
+        })
+        .collect();
+    let mut output = format!("seeds: {}\n", seeds.join(" "));
+
+    for kinds in KINDS.windows(2) {
+        let [from, to] = kinds else {
+            unreachable!("windows of 2")
+        };
+        writeln!(output, "\n{from}-to-{to} map:").expect("infallible");
+        // sorted cut points, paired up into non-overlapping source ranges
+        let mut cuts: Vec<u64> = (0..(2 * size)).map(|_| rng.below(DOMAIN)).collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut ranges: Vec<(u64, u64)> = cuts
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1] - pair[0]))
+            .collect();
+        rng.shuffle(&mut ranges);
+        for (source, len) in ranges {
+            let dest = rng.below(DOMAIN - len);
+            writeln!(output, "{dest} {source} {len}").expect("infallible");
+        }
+    }
+    output
+}
+
+/// Races capped at 6, so the kerned numbers still fit in `u128`
+fn boat_races(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size.min(6))
+        .map(|_| {
+            let time = rng.range(1..1000);
+            let peak = (time / 2) * (time - time / 2);
+            (time, rng.below(peak + 1))
+        })
+        .collect();
+    let mut time_line = String::from("Time:    ");
+    let mut distance_line = String::from("Distance:");
+    for (time, distance) in races {
+        write!(time_line, " {time:>6}").expect("infallible");
+        write!(distance_line, " {distance:>6}").expect("infallible");
+    }
+    format!("{time_line}\n{distance_line}\n")
+}
+
+fn camel_cards(rng: &mut Rng, size: usize) -> String {
+    const CARDS: &[u8] = b"AKQJT98765432";
+    let mut output = String::new();
+    for _ in 0..size {
+        // favor repeated cards, for a mix of hand types
+        let palette: Vec<u8> = (0..rng.range(1..6)).map(|_| rng.choose(CARDS)).collect();
+        let hand: String = (0..5).map(|_| rng.choose(&palette) as char).collect();
+        writeln!(output, "{hand} {}", rng.range(1..1001)).expect("infallible");
+    }
+    output
+}
+
+/// Network of loops, one per ghost, shaped like the puzzle's real inputs
+///
+/// Each ghost's loop takes a whole number of passes through the instructions, reaching its end
+/// key only at the end of the last pass. The end key leads on like the start key, so the first
+/// arrival is also the period, and the ghosts always align. The side the instruction skips may
+/// point elsewhere in the loop, as it is never taken.
+fn haunted(rng: &mut Rng, size: usize) -> String {
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    // middle keys never end in 'A' or 'Z', and the first two letters count up
+    let mut next_middle = 0;
+    let mut middle_key = || {
+        let index = next_middle;
+        next_middle += 1;
+        let third = LETTERS[1 + index % 24] as char;
+        let prefix = index / 24;
+        let first = LETTERS[(prefix / 26) % 26] as char;
+        let second = LETTERS[prefix % 26] as char;
+        format!("{first}{second}{third}")
+    };
+    let ghost_prefix = |ghost: usize| {
+        let first = LETTERS[(ghost / 26) % 26] as char;
+        let second = LETTERS[ghost % 26] as char;
+        format!("{first}{second}")
+    };
+
+    let instructions: Vec<char> = (0..rng.range(1..size as u64 / 4 + 2))
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect();
+
+    let mut maps = vec![];
+    let ghost_count = 1 + (size / 20).min(5);
+    for ghost in 0..ghost_count {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_owned(), "ZZZ".to_owned())
+        } else {
+            let prefix = ghost_prefix(ghost);
+            (format!("{prefix}A"), format!("{prefix}Z"))
+        };
+        // about `size` keys per ghost, whatever the instructions length
+        let laps = rng.range(1..(size / instructions.len()) as u64 + 2) as usize;
+        let loop_len = laps * instructions.len();
+        let mut keys = vec![start];
+        keys.extend((1..loop_len).map(|_| middle_key()));
+        keys.push(end);
+        for (index, key) in keys.iter().enumerate() {
+            // the end key is the start of the next lap
+            let step = index % loop_len;
+            let next = keys[step + 1].clone();
+            let skipped = if rng.chance(1, 4) {
+                keys[1 + rng.index(loop_len)].clone()
+            } else {
+                next.clone()
+            };
+            let (left, right) = match instructions[step % instructions.len()] {
+                'L' => (next, skipped),
+                _ => (skipped, next),
+            };
+            maps.push((key.clone(), left, right));
+        }
+    }
+    rng.shuffle(&mut maps);
+
+    let mut output: String = instructions.into_iter().collect();
+    output.push_str("\n\n");
+    for (key, left, right) in maps {
+        writeln!(output, "{key} = ({left}, {right})").expect("infallible");
+    }
+    output
+}
+
+/// Series of 21 values from polynomials of degree at most 6
+fn mirage(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let coefficients: Vec<i64> = (0..rng.range(1..8))
+            .map(|_| rng.range(0..11) as i64 - 5)
+            .collect();
+        let values: Vec<String> = (0..21)
+            .map(|x: i64| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |sum, coefficient| sum * x + coefficient)
+                    .to_string()
+            })
+            .collect();
+        writeln!(output, "{}", values.join(" ")).expect("infallible");
+    }
+    output
+}
+
+/// Maze where the loop outlines a random spanning tree, surrounded by stray pipes
+///
+/// The tree is drawn with nodes on even tiles and edges on odd tiles, so the outline never touches
+/// itself. Each tile spans two cells, leaving enclosed cells inside the outline.
+fn pipe_maze(rng: &mut Rng, size: usize) -> String {
+    const SCALE: usize = 2;
+    let nodes = (size / 4).max(1);
+    let tiles = 2 * nodes - 1;
+    let mut filled = vec![vec![false; tiles]; tiles];
+
+    // randomized depth-first spanning tree
+    let mut visited = vec![vec![false; nodes]; nodes];
+    let mut stack = vec![(0_usize, 0_usize)];
+    visited[0][0] = true;
+    filled[0][0] = true;
+    while let Some(&(row, col)) = stack.last() {
+        let mut neighbors: Vec<(usize, usize)> = [
+            row.checked_sub(1).map(|r| (r, col)),
+            (row + 1 < nodes).then_some((row + 1, col)),
+            col.checked_sub(1).map(|c| (row, c)),
+            (col + 1 < nodes).then_some((row, col + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&(r, c)| !visited[r][c])
+        .collect();
+        if neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+        rng.shuffle(&mut neighbors);
+        let (next_row, next_col) = neighbors[0];
+        visited[next_row][next_col] = true;
+        filled[2 * next_row][2 * next_col] = true;
+        filled[row + next_row][col + next_col] = true;
+        stack.push((next_row, next_col));
+    }
+
+    // corners of the scaled tiles, with a margin of stray pipes on each side
+    let corners = tiles * SCALE + 1;
+    let is_filled = |x: Option<usize>, y: Option<usize>| {
+        x.zip(y).is_some_and(|(x, y)| {
+            let (tile_x, tile_y) = (x / SCALE, y / SCALE);
+            tile_x < tiles && tile_y < tiles && filled[tile_y][tile_x]
+        })
+    };
+    let outline_char = |x: usize, y: usize| {
+        let horizontal = |x: Option<usize>| {
+            x.is_some_and(|x| is_filled(Some(x), y.checked_sub(1)) != is_filled(Some(x), Some(y)))
+        };
+        let vertical = |y: Option<usize>| {
+            y.is_some_and(|y| is_filled(x.checked_sub(1), Some(y)) != is_filled(Some(x), Some(y)))
+        };
+        let north = vertical(y.checked_sub(1));
+        let south = vertical(Some(y));
+        let west = horizontal(x.checked_sub(1));
+        let east = horizontal(Some(x));
+        match (north, south, west, east) {
+            (true, true, false, false) => Some('|'),
+            (false, false, true, true) => Some('-'),
+            (true, false, false, true) => Some('L'),
+            (true, false, true, false) => Some('J'),
+            (false, true, true, false) => Some('7'),
+            (false, true, false, true) => Some('F'),
+            _ => None,
+        }
+    };
+
+    let width = corners + 2;
+    let mut cells = vec![vec!['.'; width]; width];
+    let mut outline = vec![];
+    for (y, row) in cells.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            let corner = x.checked_sub(1).zip(y.checked_sub(1));
+            if let Some(pipe) = corner.and_then(|(x, y)| outline_char(x, y)) {
+                *cell = pipe;
+                outline.push((x, y));
+            } else if rng.chance(1, 2) {
+                *cell = rng.choose(&['|', '-', 'L', 'J', '7', 'F']);
+            }
+        }
+    }
+
+    // stray pipes next to the start could look connected, so clear them
+    let (start_x, start_y) = outline[rng.index(outline.len())];
+    let start_pipe = cells[start_y][start_x];
+    let linked = |pipe: char| match pipe {
+        '|' => [(0, -1), (0, 1)],
+        '-' => [(-1, 0), (1, 0)],
+        'L' => [(0, -1), (1, 0)],
+        'J' => [(0, -1), (-1, 0)],
+        '7' => [(0, 1), (-1, 0)],
+        'F' => [(0, 1), (1, 0)],
+        _ => unreachable!("outline pipe"),
+    };
+    for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+        if !linked(start_pipe).contains(&(dx, dy)) {
+            let x = start_x.wrapping_add_signed(dx);
+            let y = start_y.wrapping_add_signed(dy);
+            if let Some(cell) = cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *cell = '.';
+            }
+        }
+    }
+    cells[start_y][start_x] = 'S';
+
+    cells
+        .into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+/// Galaxies scattered sparsely, with at least one
+fn cosmic(rng: &mut Rng, size: usize) -> String {
+    let mut output = grid_lines(
+        rng,
+        (size, size),
+        |rng| {
+            if rng.chance(1, 10) {
+                '#'
+            } else {
+                '.'
+            }
+        },
+    );
+    let (row, col) = (rng.index(size), rng.index(size));
+    let forced = row * (size + 1) + col;
+    output.replace_range(forced..=forced, "#");
+    output
+}
+
+/// Records derived from a random arrangement, with some springs hidden as unknown
+fn springs(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let len = rng.range(1..21) as usize;
+        let mut arrangement: Vec<bool> = (0..len).map(|_| rng.chance(1, 2)).collect();
+        let forced = rng.index(len);
+        arrangement[forced] = true;
+
+        let groups: Vec<String> = arrangement
+            .split(|&damaged| !damaged)
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+        let symbols: String = arrangement
+            .iter()
+            .map(|&damaged| match (rng.chance(1, 2), damaged) {
+                (true, _) => '?',
+                (false, true) => '#',
+                (false, false) => '.',
+            })
+            .collect();
+        writeln!(output, "{symbols} {}", groups.join(",")).expect("infallible");
+    }
+    output
+}
+
+/// Patterns with exactly one reflection, and exactly one reflection off by a smudge
+fn mirrors(rng: &mut Rng, size: usize) -> String {
+    let patterns: Vec<String> = (0..size)
+        .map(|_| loop {
+            if let Some(pattern) = mirror_pattern(rng) {
+                break pattern;
+            }
+        })
+        .collect();
+    patterns.join("\n")
+}
+/// Returns `None` if the random pattern has extra reflections
+fn mirror_pattern(rng: &mut Rng) -> Option<String> {
+    // odd sizes, as in the puzzle, so a reflection always leaves rows unpaired
+    let height = 2 * rng.range(2..8) as usize + 1;
+    let width = 2 * rng.range(2..8) as usize + 1;
+    let row_line = rng.range(1..height as u64) as usize;
+    let col_line = rng.range(1..width as u64) as usize;
+    // symmetric across both lines, as each cell takes its value from the first of its mirror images
+    let canonical = |index: usize, line: usize, len: usize| {
+        let mirror = (2 * line).checked_sub(index + 1).filter(|&m| m < len);
+        mirror.map_or(index, |mirror| index.min(mirror))
+    };
+    let base: Vec<Vec<bool>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.chance(1, 2)).collect())
+        .collect();
+    let mut cells: Vec<Vec<bool>> = (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| base[canonical(row, row_line, height)][canonical(col, col_line, width)])
+                .collect()
+        })
+        .collect();
+
+    // the smudge is on an unpaired row, breaking only the column reflection
+    let unpaired_rows: Vec<usize> = (0..height)
+        .filter(|&row| {
+            (2 * row_line)
+                .checked_sub(row + 1)
+                .is_none_or(|m| m >= height)
+        })
+        .collect();
+    let smudge_row = rng.choose(&unpaired_rows);
+    let smudge_col = col_line - 1;
+    cells[smudge_row][smudge_col] = !cells[smudge_row][smudge_col];
+
+    if rng.chance(1, 2) {
+        cells = (0..width)
+            .map(|col| cells.iter().map(|row| row[col]).collect())
+            .collect();
+    }
+
+    let transposed: Vec<Vec<bool>> = (0..cells[0].len())
+        .map(|col| cells.iter().map(|row| row[col]).collect())
+        .collect();
+    let mismatches: Vec<usize> = [&cells, &transposed]
+        .into_iter()
+        .flat_map(|rows| {
+            (1..rows.len()).map(move |line| {
+                let pairs = line.min(rows.len() - line);
+                (0..pairs)
+                    .map(|offset| {
+                        let before = &rows[line - 1 - offset];
+                        let after = &rows[line + offset];
+                        before.iter().zip(after).filter(|(a, b)| a != b).count()
+                    })
+                    .sum()
+            })
+        })
+        .collect();
+    let count = |goal: usize| mismatches.iter().filter(|&&m| m == goal).count();
+    if count(0) != 1 || count(1) != 1 {
+        return None;
+    }
+
+    Some(
+        cells
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|rock| if rock { '#' } else { '.' })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect(),
+    )
+}
+
+fn rocks_roll(rng: &mut Rng, size: usize) -> String {
+    grid_lines(rng, (size, size), |rng| match rng.below(30) {
+        0..=5 => 'O',
+        6..=10 => '#',
+        _ => '.',
+    })
+}
+
+fn lens_library(rng: &mut Rng, size: usize) -> String {
+    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+    // reuse labels, so lenses get replaced and removed
+    let labels: Vec<String> = (0..(size / 3).max(1))
+        .map(|_| {
+            (0..rng.range(1..7))
+                .map(|_| rng.choose(LETTERS) as char)
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = &labels[rng.index(labels.len())];
+            if rng.chance(1, 3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.range(1..10))
+            }
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
+
+fn mirror_beams(rng: &mut Rng, size: usize) -> String {
+    grid_lines(rng, (size, size), |rng| match rng.below(20) {
+        0 => '/',
+        1 => '\\',
+        2 => '|',
+        3 => '-',
+        _ => '.',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, Rng};
+    use crate::{days, runner, PartSelection};
+
+    #[test]
+    fn rng_bounds() {
+        let mut rng = Rng::new(5);
+        for bound in 1..50 {
+            assert!(rng.below(bound) < bound);
+            assert!((10..10 + bound).contains(&rng.range(10..10 + bound)));
+        }
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reproducible() {
+        for entry in days::ALL {
+            let first = generate(entry.day, 20, 7).unwrap();
+            assert_eq!(
+                first,
+                generate(entry.day, 20, 7).unwrap(),
+                "day {}",
+                entry.day
+            );
+        }
+        assert_ne!(generate(14, 20, 7).unwrap(), generate(14, 20, 8).unwrap());
+        assert!(generate(25, 20, 7).is_err());
+    }
+
+    #[test]
+    fn solvable() {
+        for entry in days::ALL {
+            // the default size and past it, except where the solver's search is too slow for
+            // debug builds
+            let max_size = match entry.day {
+                12 | 16 => 20,
+                11 | 14 => 100,
+                _ => 300,
+            };
+            for (size, seed) in [(1, 0), (8, 1), (20, 2), (100, 3), (300, 4)] {
+                if size > max_size {
+                    continue;
+                }
+                let input = generate(entry.day, size, seed).unwrap();
+                if let Err(err) = entry.run(&input, PartSelection::Both) {
+                    panic!(
+                        "day {} size {size} seed {seed}: {err:#}\n{input}",
+                        entry.day
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pipe_maze_encloses_tiles() {
+        let input = generate(10, 12, 3).unwrap();
+        let answers = runner::find(10)
+            .expect("day 10 exists")
+            .run(&input, PartSelection::Both)
+            .unwrap();
+        let enclosed = answers.part2.unwrap().value.as_u64().unwrap();
+        assert!(enclosed > 0, "{input}");
+    }
+}
@@ -0,0 +1,604 @@
+//! Rectangular grid of cells, stored row by row
+
+use crate::{
+    dimension::Dimension, direction::Direction, either::Either, parse_error::parse_chars_with,
+    point::Point, ParseError,
+};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+impl<T> Grid<T> {
+    /// Creates a grid by calling the function for each point, row by row
+    pub fn from_fn(width: usize, height: usize, mut cell_fn: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Point { row, col }))
+            .map(&mut cell_fn)
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+    /// Parses the entire input, see [`Self::parse_lines`]
+    pub fn parse(
+        input: &str,
+        cell_fn: impl FnMut(char) -> Result<T, ParseError>,
+    ) -> Result<Option<Self>, ParseError> {
+        Self::parse_lines(input.lines().enumerate(), cell_fn)
+    }
+    /// Parses lines until the first blank line, with each line's index in the input
+    ///
+    /// Returns `None` if there are no lines before the blank line.
+    pub fn parse_lines<'a>(
+        lines: impl Iterator<Item = (usize, &'a str)>,
+        mut cell_fn: impl FnMut(char) -> Result<T, ParseError>,
+    ) -> Result<Option<Self>, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (line_index, line) in lines {
+            if line.is_empty() {
+                break;
+            }
+            let row = parse_chars_with(line, &mut cell_fn)
+                .map_err(|err| err.at(line_index, line, line))?;
+            match width {
+                None => {
+                    width = Some(row.len());
+                }
+                Some(existing) if row.len() != existing => {
+                    return Err(ParseError::new(format!(
+                        "invalid width {current}, expected width {existing}",
+                        current = row.len()
+                    ))
+                    .at(line_index, line, line));
+                }
+                Some(_match) => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(width.map(|width| Self {
+            cells,
+            width,
+            height,
+        }))
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Number of rows or columns, for the [`Dimension::Row`] or [`Dimension::Col`] respectively
+    pub fn size(&self, dimension: Dimension) -> usize {
+        match dimension {
+            Dimension::Row => self.height,
+            Dimension::Col => self.width,
+        }
+    }
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+    fn index(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            point.index_for_width(self.width)
+        } else {
+            None
+        }
+    }
+    pub fn get(&self, point: Point) -> Option<&T> {
+        let index = self.index(point)?;
+        self.cells.get(index)
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let index = self.index(point)?;
+        self.cells.get_mut(index)
+    }
+    /// Returns the neighboring point and cell in the direction (if within the grid)
+    pub fn neighbor(&self, point: Point, direction: Direction) -> Option<(Point, &T)> {
+        let neighbor = direction.of(point)?;
+        self.get(neighbor).map(|cell| (neighbor, cell))
+    }
+    /// Returns the neighbors in each of [`Direction::ALL`] that are within the grid
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = (Direction, Point, &T)> + '_ {
+        Direction::ALL.iter().filter_map(move |&direction| {
+            let (neighbor, cell) = self.neighbor(point, direction)?;
+            Some((direction, neighbor, cell))
+        })
+    }
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row >= self.height {
+            return None;
+        }
+        let start = row * self.width;
+        self.cells.get(start..(start + self.width))
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).filter_map(|row| self.row(row))
+    }
+    /// Returns the cells of the column, from top to bottom (empty if out of bounds)
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> + Clone + '_ {
+        self.series(Dimension::Col, col)
+    }
+    /// Returns the points of row `n` (left to right) or column `n` (top to bottom)
+    pub fn series_points(
+        &self,
+        dimension: Dimension,
+        n: usize,
+    ) -> impl Iterator<Item = Point> + Clone {
+        let len = if n < self.size(dimension) {
+            self.size(dimension.opposite())
+        } else {
+            0
+        };
+        (0..len).map(move |amount| match dimension {
+            Dimension::Row => Point {
+                row: n,
+                col: amount,
+            },
+            Dimension::Col => Point {
+                row: amount,
+                col: n,
+            },
+        })
+    }
+    /// Returns the cells of row `n` (left to right) or column `n` (top to bottom)
+    pub fn series(&self, dimension: Dimension, n: usize) -> impl Iterator<Item = &T> + Clone + '_ {
+        match dimension {
+            Dimension::Row => Either::A(self.row(n).unwrap_or(&[]).iter()),
+            Dimension::Col => Either::B(
+                self.series_points(dimension, n)
+                    .map(|point| &self.cells[point.row * self.width + point.col]),
+            ),
+        }
+    }
+    /// Returns each point and cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| (Point::from_index_width(index, width), cell))
+    }
+    pub fn map<U>(&self, mut map_fn: impl FnMut(Point, &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self
+                .iter()
+                .map(|(point, cell)| map_fn(point, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+    /// Returns a grid of the same shape, with new cells from the generator
+    pub fn empty_with<U>(&self, mut generator_fn: impl FnMut() -> U) -> Grid<U> {
+        self.map(|_, _| generator_fn())
+    }
+}
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns, mirroring along the main diagonal
+    pub fn transpose(&self) -> Self {
+        self.view().transpose().to_grid()
+    }
+    /// Rotates a quarter turn, so the left column becomes the top row
+    pub fn rotate_clockwise(&self) -> Self {
+        self.view().rotate_clockwise().to_grid()
+    }
+    /// Rotates a quarter turn, so the top row becomes the left column
+    pub fn rotate_counterclockwise(&self) -> Self {
+        self.view().rotate_counterclockwise().to_grid()
+    }
+    /// Reverses the order of the rows, so the top row becomes the bottom row
+    pub fn flip_vertical(&self) -> Self {
+        self.view().flip_vertical().to_grid()
+    }
+    /// Reverses the order of the columns, so the left column becomes the right column
+    pub fn flip_horizontal(&self) -> Self {
+        self.view().flip_horizontal().to_grid()
+    }
+}
+impl<T> std::ops::Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "point {point:?} out of bounds for grid {width}x{height}",
+                width = self.width,
+                height = self.height
+            )
+        })
+    }
+}
+impl<T> std::ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point:?} out of bounds for grid {width}x{height}"))
+    }
+}
+
+/// Borrowed grid seen in another orientation (rotated, transposed or flipped), without copying
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    orientation: Orientation,
+}
+// NOTE: manual impls to avoid requiring `T: Clone`
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for View<'_, T> {}
+/// One of the eight symmetries of a rectangle, mapping [`View`] points to [`Grid`] points
+///
+/// Points are transposed first (if enabled), then the rows and columns are reversed as
+/// needed within the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    transpose: bool,
+    reverse_rows: bool,
+    reverse_cols: bool,
+}
+impl<T> Grid<T> {
+    /// Returns a view of the grid in the original orientation
+    pub fn view(&self) -> View<'_, T> {
+        View {
+            grid: self,
+            orientation: Orientation::default(),
+        }
+    }
+}
+impl<'a, T> View<'a, T> {
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+    pub fn width(&self) -> usize {
+        self.size(Dimension::Col)
+    }
+    pub fn height(&self) -> usize {
+        self.size(Dimension::Row)
+    }
+    /// Number of rows or columns in the view, see [`Grid::size`]
+    pub fn size(&self, dimension: Dimension) -> usize {
+        let dimension = if self.orientation.transpose {
+            dimension.opposite()
+        } else {
+            dimension
+        };
+        self.grid.size(dimension)
+    }
+    /// Swaps rows and columns of the view
+    pub fn transpose(self) -> Self {
+        let Orientation {
+            transpose,
+            reverse_rows,
+            reverse_cols,
+        } = self.orientation;
+        self.with_orientation(Orientation {
+            transpose: !transpose,
+            reverse_rows,
+            reverse_cols,
+        })
+    }
+    /// Reverses the order of the rows in the view
+    pub fn flip_vertical(self) -> Self {
+        let mut orientation = self.orientation;
+        if orientation.transpose {
+            orientation.reverse_cols = !orientation.reverse_cols;
+        } else {
+            orientation.reverse_rows = !orientation.reverse_rows;
+        }
+        self.with_orientation(orientation)
+    }
+    /// Reverses the order of the columns in the view
+    pub fn flip_horizontal(self) -> Self {
+        self.transpose().flip_vertical().transpose()
+    }
+    /// Rotates the view a quarter turn, so the left column becomes the top row
+    pub fn rotate_clockwise(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+    /// Rotates the view a quarter turn, so the top row becomes the left column
+    pub fn rotate_counterclockwise(self) -> Self {
+        self.transpose().flip_vertical()
+    }
+    fn with_orientation(self, orientation: Orientation) -> Self {
+        Self {
+            grid: self.grid,
+            orientation,
+        }
+    }
+    /// Returns the point in the underlying grid for the point in the view
+    pub fn grid_point(&self, point: Point) -> Option<Point> {
+        let Orientation {
+            transpose,
+            reverse_rows,
+            reverse_cols,
+        } = self.orientation;
+        let Point { row, col } = point;
+        let (row, col) = if transpose { (col, row) } else { (row, col) };
+        let reverse = |reverse: bool, value: usize, len: usize| {
+            if reverse {
+                len.checked_sub(value + 1)
+            } else {
+                (value < len).then_some(value)
+            }
+        };
+        let row = reverse(reverse_rows, row, self.grid.height)?;
+        let col = reverse(reverse_cols, col, self.grid.width)?;
+        Some(Point { row, col })
+    }
+    pub fn get(&self, point: Point) -> Option<&'a T> {
+        let point = self.grid_point(point)?;
+        self.grid.get(point)
+    }
+    /// Returns the cells of row `n` (left to right) or column `n` (top to bottom) in the view
+    pub fn series(&self, dimension: Dimension, n: usize) -> impl Iterator<Item = &'a T> + Clone {
+        let view = *self;
+        let len = if n < self.size(dimension) {
+            self.size(dimension.opposite())
+        } else {
+            0
+        };
+        (0..len).map(move |amount| {
+            let point = match dimension {
+                Dimension::Row => Point {
+                    row: n,
+                    col: amount,
+                },
+                Dimension::Col => Point {
+                    row: amount,
+                    col: n,
+                },
+            };
+            view.get(point).expect("point within view")
+        })
+    }
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &'a T> + Clone {
+        self.series(Dimension::Row, row)
+    }
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &'a T> + Clone {
+        self.series(Dimension::Col, col)
+    }
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + Clone> + '_ {
+        (0..self.height()).map(|row| self.row(row))
+    }
+    /// Returns each point and cell of the view, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + '_ {
+        let width = self.width();
+        (0..self.height())
+            .flat_map(move |row| (0..width).map(move |col| Point { row, col }))
+            .map(|point| (point, self.get(point).expect("point within view")))
+    }
+}
+impl<T: Clone> View<'_, T> {
+    /// Copies the cells into a new grid, in the orientation of the view
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width(), self.height(), |point| {
+            self.get(point).expect("point within view").clone()
+        })
+    }
+}
+impl<T> std::ops::Index<Point> for View<'_, T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "point {point:?} out of bounds for view {width}x{height}",
+                width = self.width(),
+                height = self.height()
+            )
+        })
+    }
+}
+
+/// Writes the rows with the column and row numbers (last digit only) as headers
+fn fmt_rows<'a, T: 'a>(
+    f: &mut std::fmt::Formatter<'_>,
+    width: usize,
+    rows: impl Iterator<Item = impl IntoIterator<Item = &'a T>>,
+    fmt_cell: impl Fn(&mut std::fmt::Formatter<'_>, &T) -> std::fmt::Result,
+) -> std::fmt::Result {
+    write!(f, "  ")?;
+    for col in 0..width {
+        write!(f, "{}", col % 10)?;
+    }
+    writeln!(f)?;
+
+    for (row, cells) in rows.enumerate() {
+        write!(f, "{} ", row % 10)?;
+        for cell in cells {
+            fmt_cell(f, cell)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+impl<T: std::fmt::Debug> std::fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_rows(f, self.width, self.rows(), |f, cell| write!(f, "{cell:?}"))
+    }
+}
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_rows(f, self.width, self.rows(), |f, cell| write!(f, "{cell}"))
+    }
+}
+impl<T: std::fmt::Debug> std::fmt::Debug for View<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_rows(f, self.width(), self.rows(), |f, cell| {
+            write!(f, "{cell:?}")
+        })
+    }
+}
+impl<T: std::fmt::Display> std::fmt::Display for View<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_rows(f, self.width(), self.rows(), |f, cell| write!(f, "{cell}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, View};
+    use crate::{dimension::Dimension, direction, point::Point, ParseError};
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok).unwrap().expect("nonempty")
+    }
+
+    #[test]
+    fn parse_lines_until_blank() {
+        let mut lines = "ab\ncd\n\nef".lines().enumerate();
+        let grid = Grid::parse_lines(lines.by_ref(), Ok).unwrap().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.cells(), &['a', 'b', 'c', 'd']);
+        assert_eq!(lines.next(), Some((3, "ef")));
+
+        assert_eq!(Grid::parse("", Ok).unwrap(), None::<Grid<char>>);
+    }
+    #[test]
+    fn parse_errors_located() {
+        let digit = |c: char| c.to_digit(10).ok_or_else(|| ParseError::unexpected_char(c));
+
+        let err = Grid::parse("123\n4x6", digit).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!((location.line, location.column), (2, 2));
+
+        let err = Grid::parse("123\n45", digit).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: invalid width 2, expected width 3"
+        );
+    }
+    #[test]
+    fn series() {
+        let grid = parse("abc\ndef");
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.col(1).collect::<String>(), "be");
+        assert_eq!(grid.col(3).count(), 0);
+        assert_eq!(grid.series(Dimension::Row, 0).collect::<String>(), "abc");
+        assert_eq!(
+            grid.series_points(Dimension::Col, 2).collect::<Vec<_>>(),
+            vec![Point { row: 0, col: 2 }, Point { row: 1, col: 2 }]
+        );
+        assert_eq!(grid.get(Point { row: 0, col: 3 }), None);
+        assert_eq!(grid[Point { row: 1, col: 0 }], 'd');
+    }
+    #[test]
+    fn neighbors() {
+        let grid = parse("abc\ndef");
+        let corner = grid
+            .neighbors(Point { row: 0, col: 0 })
+            .map(|(direction, _, &cell)| (direction, cell))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![(direction::SOUTH, 'd'), (direction::EAST, 'b')]
+        );
+        assert_eq!(grid.neighbors(Point { row: 1, col: 1 }).count(), 3);
+        assert_eq!(
+            grid.neighbor(Point { row: 1, col: 1 }, direction::NORTH),
+            Some((Point { row: 0, col: 1 }, &'b'))
+        );
+    }
+    #[test]
+    fn transform() {
+        let grid = parse("abc\ndef");
+        assert_eq!(grid.transpose(), parse("ad\nbe\ncf"));
+        assert_eq!(grid.rotate_clockwise(), parse("da\neb\nfc"));
+        assert_eq!(grid.rotate_counterclockwise(), parse("cf\nbe\nad"));
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+    #[test]
+    fn views() {
+        let grid = parse("abc\ndef");
+        let view = grid.view().rotate_clockwise();
+        assert_eq!((view.width(), view.height()), (2, 3));
+        assert_eq!(view.row(0).collect::<String>(), "da");
+        assert_eq!(view.col(1).collect::<String>(), "abc");
+        assert_eq!(view[Point { row: 2, col: 0 }], 'f');
+        assert_eq!(view.get(Point { row: 0, col: 2 }), None);
+        assert_eq!(
+            view.grid_point(Point { row: 0, col: 0 }),
+            Some(Point { row: 1, col: 0 })
+        );
+        assert_eq!(grid.view().flip_vertical().to_grid(), parse("def\nabc"));
+        assert_eq!(grid.view().flip_horizontal().to_grid(), parse("cba\nfed"));
+        assert_eq!(view.to_string(), "  01\n0 da\n1 eb\n2 fc\n");
+    }
+    #[test]
+    fn display() {
+        let grid = parse("abc\ndef");
+        assert_eq!(grid.to_string(), "  012\n0 abc\n1 def\n");
+        assert_eq!(
+            format!("{:?}", grid.map(|point, _| point.col)),
+            "  012\n0 012\n1 012\n"
+        );
+    }
+
+    fn arb_grid() -> impl Strategy<Value = Grid<u8>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(any::<u8>(), width * height).prop_map(move |cells| {
+                let mut cells = cells.into_iter();
+                Grid::from_fn(width, height, |_| cells.next().expect("enough cells"))
+            })
+        })
+    }
+    type Transform<'a> = fn(View<'a, u8>) -> View<'a, u8>;
+    fn transforms<'a>() -> [(&'static str, Transform<'a>); 5] {
+        [
+            ("transpose", View::transpose),
+            ("rotate_clockwise", View::rotate_clockwise),
+            ("rotate_counterclockwise", View::rotate_counterclockwise),
+            ("flip_vertical", View::flip_vertical),
+            ("flip_horizontal", View::flip_horizontal),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn four_rotations_identity(grid in arb_grid()) {
+            let clockwise = grid.view().rotate_clockwise().rotate_clockwise().rotate_clockwise().rotate_clockwise();
+            prop_assert_eq!(clockwise.orientation(), grid.view().orientation());
+            prop_assert_eq!(clockwise.to_grid(), grid.clone());
+
+            let counterclockwise = (0..4).fold(grid.clone(), |grid, _| grid.rotate_counterclockwise());
+            prop_assert_eq!(counterclockwise, grid);
+        }
+        #[test]
+        fn transforms_match_copies(grid in arb_grid()) {
+            for (name, transform) in transforms() {
+                let view = transform(grid.view());
+                let copied = view.to_grid();
+                prop_assert_eq!((view.width(), view.height()), (copied.width(), copied.height()), "{}", name);
+                for (point, cell) in view.iter() {
+                    prop_assert_eq!(copied.get(point), Some(cell), "{} at {:?}", name, point);
+                    let grid_point = view.grid_point(point).expect("point within view");
+                    prop_assert_eq!(&grid[grid_point], cell, "{} at {:?}", name, point);
+                }
+            }
+        }
+        #[test]
+        fn inverses(grid in arb_grid()) {
+            let view = grid.view();
+            prop_assert_eq!(view.rotate_clockwise().rotate_counterclockwise().orientation(), view.orientation());
+            prop_assert_eq!(view.transpose().transpose().orientation(), view.orientation());
+            prop_assert_eq!(view.flip_vertical().flip_vertical().orientation(), view.orientation());
+            prop_assert_eq!(view.flip_horizontal().flip_horizontal().orientation(), view.orientation());
+            prop_assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid.clone());
+            prop_assert_eq!(grid.transpose().transpose(), grid);
+        }
+    }
+}
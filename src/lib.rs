@@ -1,22 +1,249 @@
+use anyhow::Context;
 use clap::Parser;
-use std::{collections::VecDeque, io::Read, path::PathBuf};
+use std::{
+    collections::VecDeque,
+    io::{IsTerminal, Read},
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+};
+
+pub mod bench;
+pub mod generate;
+pub mod grid;
+pub mod manifest;
+pub mod ranges;
+pub mod runner;
+
+pub use parse_error::ParseError;
+
+pub mod days {
+    use crate::runner::Entry;
+
+    pub mod day10_pipe_maze;
+    pub mod day11_cosmic;
+    pub mod day12_springs;
+    pub mod day13_mirrors;
+    pub mod day14_rocks_roll;
+    pub mod day15_lens_library;
+    pub mod day16_mirror_beams;
+    pub mod day1_trebuchet;
+    pub mod day2_cube_conundrum;
+    pub mod day3_gear_ratios;
+    pub mod day3_simpler;
+    pub mod day4_scratchcards;
+    pub mod day5_seed_locations;
+    pub mod day6_boat_races;
+    pub mod day7_camel_cards;
+    pub mod day8_haunted;
+    pub mod day9_mirage;
+
+    /// All available solvers, in order of the day
+    pub const ALL: &[Entry] = &[
+        Entry::new::<day1_trebuchet::Solution>("trebuchet"),
+        Entry::new::<day2_cube_conundrum::Solution>("cube_conundrum"),
+        Entry::new::<day3_simpler::Solution>("gear_ratios"),
+        Entry::new::<day4_scratchcards::Solution>("scratchcards"),
+        Entry::new::<day5_seed_locations::Solution>("seed_locations"),
+        Entry::new::<day6_boat_races::Solution>("boat_races"),
+        Entry::new::<day7_camel_cards::Solution>("camel_cards"),
+        Entry::new::<day8_haunted::Solution>("haunted"),
+        Entry::new::<day9_mirage::Solution>("mirage"),
+        Entry::new::<day10_pipe_maze::Solution>("pipe_maze"),
+        Entry::new::<day11_cosmic::Solution>("cosmic"),
+        Entry::new::<day12_springs::Solution>("springs"),
+        Entry::new::<day13_mirrors::Solution>("mirrors"),
+        Entry::new::<day14_rocks_roll::Solution>("rocks_roll")
+            .with_animation(day14_rocks_roll::animate),
+        Entry::new::<day15_lens_library::Solution>("lens_library"),
+        Entry::new::<day16_mirror_beams::Solution>("mirror_beams"),
+    ];
+}
+
+/// Solution to a single day's puzzle, split into phases
+///
+/// The parsed input is shared by both parts, so each part can run (and be measured) on its own.
+pub trait Solver {
+    /// Day of the advent calendar
+    const DAY: u8;
+    /// Puzzle input, after parsing
+    type Parsed;
+    type Answer1: std::fmt::Display + serde::Serialize;
+    type Answer2: std::fmt::Display + serde::Serialize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    /// Parses the input, with the puzzle rules adjusted from the command line
+    ///
+    /// Days without adjustable rules reject any [`RuleArgs`] given.
+    fn parse_with_rules(input: &str, rules: &RuleArgs) -> anyhow::Result<Self::Parsed> {
+        rules.ensure_unset(Self::DAY)?;
+        Self::parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Answer2>;
+}
 
 #[derive(Parser, Debug)]
-struct Args {
-    filename: Option<PathBuf>,
+#[command(name = "advent", about = "Advent of Code 2023 solutions")]
+pub struct Args {
+    /// Output format for the answers (diagnostic output is always on stderr)
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub format: Format,
+    /// Show more diagnostic output on stderr (`-vv` for step-by-step traces)
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+    /// Hide all diagnostic output, printing only the answers
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per day, on a single line
+    Json,
+}
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Runs the solver for one day (or all days)
+    Run(RunArgs),
+    /// Measures the parse, part 1 and part 2 phases over repeated runs
+    Bench(BenchArgs),
+    /// Writes a random input for the day to stdout
+    Gen(GenArgs),
+}
+#[derive(clap::Args, Debug)]
+pub struct InputArgs {
+    /// Day of the puzzle to solve
+    #[arg(required_unless_present = "all")]
+    pub day: Option<u8>,
+    /// Input files for the day (reads stdin if omitted)
+    ///
+    /// Each file is solved separately, use `-` to read stdin
+    #[arg(value_name = "FILENAME")]
+    pub filenames: Vec<PathBuf>,
+    /// Runs all days, using the `day{N}.txt` input files in the specified directory
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["day", "filenames"])]
+    pub all: Option<PathBuf>,
+    /// Which parts of the puzzle to solve
+    #[arg(long, value_enum, default_value_t)]
+    pub part: PartSelection,
+    #[command(flatten)]
+    pub rules: RuleArgs,
+}
+/// Adjustments to the puzzle rules, for the days that support them
+#[derive(clap::Args, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuleArgs {
+    /// Tilt directions of each spin cycle, as letters `N`, `W`, `S` and `E` (day 14)
+    #[arg(long, value_name = "DIRECTIONS", conflicts_with = "all")]
+    pub sequence: Option<String>,
+    /// Edge of the platform to measure the load against, `N`, `W`, `S` or `E` (day 14)
+    #[arg(long, conflicts_with = "all")]
+    pub edge: Option<String>,
+}
+impl RuleArgs {
+    /// Returns an error naming the first rule set, for days without adjustable rules
+    pub fn ensure_unset(&self, day: u8) -> anyhow::Result<()> {
+        let Self { sequence, edge } = self;
+        let flag = [("--sequence", sequence), ("--edge", edge)]
+            .into_iter()
+            .find_map(|(flag, value)| value.is_some().then_some(flag));
+        match flag {
+            Some(flag) => anyhow::bail!("day {day} does not support {flag}"),
+            None => Ok(()),
+        }
+    }
+}
+#[derive(clap::Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+    /// Checks the answers against the expected answers in the manifest file
+    ///
+    /// Either TOML (`[day14]` tables with `part1`/`part2` keys), or lines of `day part answer`
+    #[arg(long, value_name = "MANIFEST")]
+    pub check: Option<PathBuf>,
+    /// Animates the input on the terminal instead of printing the answers (day 14 only)
+    #[arg(long, conflicts_with = "check")]
+    pub animate: bool,
+}
+#[derive(clap::Args, Debug)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+    /// Number of times to run each day
+    #[arg(short = 'n', long, default_value = "10")]
+    pub iterations: NonZeroU32,
+    /// Writes the min/median/max of each phase to a CSV file
+    #[arg(long, value_name = "FILE")]
+    pub csv: Option<PathBuf>,
+}
+#[derive(clap::Args, Debug)]
+pub struct GenArgs {
+    /// Day of the puzzle format to generate
+    pub day: u8,
+    /// Number of lines (or grid width) of the input
+    #[arg(short, long, default_value = "100")]
+    pub size: NonZeroUsize,
+    /// Seed for the random input (picked from the clock and logged if omitted)
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+/// One of the two halves of each day's puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+/// Parts of the puzzle requested on the command line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PartSelection {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+impl PartSelection {
+    pub fn includes(self, part: Part) -> bool {
+        matches!(
+            (self, part),
+            (Self::Both, _) | (Self::One, Part::One) | (Self::Two, Part::Two)
+        )
+    }
+}
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
 }
 
-/// Returns the input string read from the cli argument file, or stdin
-pub fn get_input_string() -> anyhow::Result<String> {
-    let args = Args::parse();
+/// Filename referring to stdin, for [`get_input_string`]
+pub const STDIN_FILENAME: &str = "-";
 
-    let input = if let Some(filename) = args.filename {
-        std::fs::read_to_string(filename)?
-    } else {
-        println!("Awaiting instructions from stdin:");
-        let mut input_buf = String::new();
-        std::io::stdin().read_to_string(&mut input_buf)?;
-        input_buf
+/// Returns the input string read from the file (if specified and not [`STDIN_FILENAME`]), or stdin
+pub fn get_input_string(filename: Option<&Path>) -> anyhow::Result<String> {
+    let input = match filename {
+        Some(filename) if filename != Path::new(STDIN_FILENAME) => {
+            std::fs::read_to_string(filename)
+                .with_context(|| format!("reading input {filename:?}"))?
+        }
+        _ => {
+            let stdin = std::io::stdin();
+            if stdin.is_terminal() {
+                log_info!("Awaiting instructions from stdin:");
+            }
+            let mut input_buf = String::new();
+            stdin.lock().read_to_string(&mut input_buf)?;
+            input_buf
+        }
     };
     Ok(input)
 }
@@ -219,7 +446,8 @@ where
             }
 
             if clear_last_indices {
-                self.last_indices.clear();
+                // keep the matched char, so it may still open the next lookback string
+                self.last_indices.truncate(1);
             }
 
             if let Some(result) = self.pending_results.pop_front() {
@@ -317,49 +545,461 @@ pub mod print {
     }
 }
 
+pub mod parse_error {
+    //! Puzzle input errors, located by line and column
+
+    use crate::{
+        print::{ConsolePrinter, Highlight},
+        CharIndexEnd, CharIndices,
+    };
+
+    /// Failure to parse the puzzle input, ideally located at the offending text
+    ///
+    /// Parsers for a piece of a line report the [`span`](Self::with_span) within that piece,
+    /// and the caller that knows the full line adds the [`location`](Self::at).
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ParseError {
+        message: String,
+        /// Byte range within the text being parsed (before the location is known)
+        span: Option<std::ops::Range<usize>>,
+        /// Boxed to keep `Result<_, ParseError>` small on the happy path
+        location: Option<Box<Location>>,
+    }
+    /// Position of a [`ParseError`] within the input
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Location {
+        /// Line number, starting from 1
+        pub line: usize,
+        /// Column of the first offending char, starting from 1
+        pub column: usize,
+        /// Full text of the line
+        pub line_text: String,
+        /// Offending text within the line
+        pub text: String,
+        /// Byte range of the offending text within the line
+        byte_range: std::ops::Range<usize>,
+    }
+    impl ParseError {
+        pub fn new(message: impl Into<String>) -> Self {
+            Self {
+                message: message.into(),
+                span: None,
+                location: None,
+            }
+        }
+        /// Error for a character that is not allowed
+        pub fn unexpected_char(c: char) -> Self {
+            Self::new(format!("unexpected character {c:?}"))
+        }
+        /// Marks the offending bytes within the text being parsed
+        pub fn with_span(mut self, span: std::ops::Range<usize>) -> Self {
+            self.span.get_or_insert(span);
+            self
+        }
+        /// Locates the error within the line (`line_index` starts from 0)
+        ///
+        /// The `text` is the slice of `line` that failed to parse. If a [span](Self::with_span)
+        /// was marked, only that portion of `text` is offending. Errors that are already located
+        /// are unchanged.
+        pub fn at(mut self, line_index: usize, line: &str, text: &str) -> Self {
+            if self.location.is_some() {
+                return self;
+            }
+            let text_start = (text.as_ptr() as usize)
+                .checked_sub(line.as_ptr() as usize)
+                .filter(|&start| start + text.len() <= line.len());
+            let byte_range = match (text_start, self.span.take()) {
+                (Some(start), Some(span)) if span.end <= text.len() => {
+                    (start + span.start)..(start + span.end)
+                }
+                (Some(start), _) => start..(start + text.len()),
+                (None, _) => 0..line.len(),
+            };
+            let column = line[..byte_range.start].chars().count() + 1;
+            self.location = Some(Box::new(Location {
+                line: line_index + 1,
+                column,
+                line_text: line.to_owned(),
+                text: line[byte_range.clone()].to_owned(),
+                byte_range,
+            }));
+            self
+        }
+        pub fn message(&self) -> &str {
+            &self.message
+        }
+        pub fn location(&self) -> Option<&Location> {
+            self.location.as_deref()
+        }
+        /// Prints the offending line, with a caret under the offending text
+        pub fn render(&self, printer: &mut ConsolePrinter) -> anyhow::Result<()> {
+            let Some(location) = &self.location else {
+                return Ok(());
+            };
+            let Location {
+                line,
+                ref line_text,
+                ref byte_range,
+                ..
+            } = **location;
+
+            let gutter = format!("{line} | ");
+            let style = console::Style::new().red().bold();
+
+            let full_line = format!("{gutter}{line_text}");
+            let offset = gutter.len();
+            let spans = highlight(
+                &full_line,
+                (offset + byte_range.start)..(offset + byte_range.end),
+                style.clone(),
+            );
+            printer.print_line(&full_line, &spans)?;
+
+            let caret_width = line_text[byte_range.clone()].chars().count().max(1);
+            let caret_line = format!(
+                "{:gutter_width$}{:column$}{}",
+                "",
+                "",
+                "^".repeat(caret_width),
+                gutter_width = gutter.chars().count(),
+                column = location.column - 1,
+            );
+            let caret_start = caret_line.len() - caret_width;
+            let spans = highlight(&caret_line, caret_start..caret_line.len(), style);
+            printer.print_line(&caret_line, &spans)
+        }
+    }
+    fn highlight(
+        line: &str,
+        byte_range: std::ops::Range<usize>,
+        style: console::Style,
+    ) -> Vec<Highlight> {
+        let mut chars = CharIndices::new(line);
+        let Some((start, _)) = chars.find(|(index, _)| index.byte_index >= byte_range.start) else {
+            return vec![];
+        };
+        let end = std::iter::once((start, '\0'))
+            .chain(chars.by_ref())
+            .find(|(index, _)| index.byte_index >= byte_range.end)
+            .map(|(index, _)| CharIndexEnd::Position(index))
+            .or_else(|| chars.take_end_char_sequence().map(CharIndexEnd::End));
+        end.map(|end| Highlight { start, end, style })
+            .into_iter()
+            .collect()
+    }
+    impl std::fmt::Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let Self {
+                message, location, ..
+            } = self;
+            match location.as_deref() {
+                Some(Location { line, column, .. }) => {
+                    write!(f, "line {line}, column {column}: {message}")
+                }
+                None => write!(f, "{message}"),
+            }
+        }
+    }
+    impl std::error::Error for ParseError {}
+
+    /// Parses each char of the text, marking the span of the first offending char
+    pub fn parse_chars<T>(text: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: TryFrom<char, Error = ParseError>,
+    {
+        parse_chars_with(text, T::try_from)
+    }
+    /// Parses each char of the text using the function, see [`parse_chars`]
+    pub fn parse_chars_with<T>(
+        text: &str,
+        mut parse_fn: impl FnMut(char) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        text.char_indices()
+            .map(|(index, c)| {
+                parse_fn(c).map_err(|err| err.with_span(index..(index + c.len_utf8())))
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{parse_chars, ParseError};
+
+        struct Digit(u32);
+        impl TryFrom<char> for Digit {
+            type Error = ParseError;
+            fn try_from(c: char) -> Result<Self, ParseError> {
+                c.to_digit(10)
+                    .map(Digit)
+                    .ok_or_else(|| ParseError::unexpected_char(c))
+            }
+        }
+
+        #[test]
+        fn unlocated() {
+            let err = ParseError::new("bad");
+            assert_eq!(err.to_string(), "bad");
+            assert_eq!(err.location(), None);
+        }
+
+        #[test]
+        fn locate_char() {
+            let line = "seeds: 12x4";
+            let digits = &line[7..];
+            let err = parse_chars::<Digit>(digits)
+                .err()
+                .expect("invalid digit")
+                .at(2, line, digits);
+            assert_eq!(
+                err.to_string(),
+                "line 3, column 10: unexpected character 'x'"
+            );
+            let location = err.location().unwrap();
+            assert_eq!(location.text, "x");
+            assert_eq!(location.line_text, line);
+
+            let valid = parse_chars::<Digit>("123").unwrap();
+            assert_eq!(valid.iter().map(|d| d.0).sum::<u32>(), 6);
+        }
+
+        #[test]
+        fn locate_text() {
+            let line = "AAA = (BBB, CC)";
+            let err = ParseError::new("invalid key").at(0, line, &line[12..14]);
+            let location = err.location().unwrap();
+            assert_eq!((location.line, location.column), (1, 13));
+            assert_eq!(location.text, "CC");
+
+            // first location is kept
+            let relocated = err.clone().at(5, line, line);
+            assert_eq!(relocated, err);
+        }
+
+        #[test]
+        fn locate_unicode_column() {
+            let line = "ééx";
+            let err = ParseError::unexpected_char('x').at(0, line, &line[4..]);
+            assert_eq!(err.location().unwrap().column, 3);
+        }
+
+        #[test]
+        fn locate_foreign_text() {
+            let other = String::from("zzz");
+            let err = ParseError::new("oops").at(0, "abc", &other);
+            let location = err.location().unwrap();
+            assert_eq!((location.column, location.text.as_str()), (1, "abc"));
+        }
+    }
+}
+
+pub mod log {
+    //! Diagnostic output on stderr, filtered by the verbosity flags in [`crate::Args`]
+    //!
+    //! Use the [`log_info!`](crate::log_info), [`log_debug!`](crate::log_debug) and
+    //! [`log_trace!`](crate::log_trace) macros, or check [`enabled`] before expensive output.
+
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    /// Detail of a diagnostic message, from least to most verbose
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Level {
+        /// Progress and summaries, shown unless `--quiet`
+        Info,
+        /// Intermediate values, shown for `-v`
+        Debug,
+        /// Step-by-step traces, shown for `-vv`
+        Trace,
+    }
+
+    /// Number of levels shown (`0` for quiet)
+    static VERBOSITY: AtomicU8 = AtomicU8::new(1);
+
+    /// Sets the verbosity from the count of `-v` flags, or hides all levels for `quiet`
+    pub fn init(verbose: u8, quiet: bool) {
+        VERBOSITY.store(verbosity(verbose, quiet), Ordering::Relaxed);
+    }
+    fn verbosity(verbose: u8, quiet: bool) -> u8 {
+        if quiet {
+            0
+        } else {
+            verbose.saturating_add(1)
+        }
+    }
+
+    /// Returns `true` if messages at the specified level are shown
+    pub fn enabled(level: Level) -> bool {
+        shows(VERBOSITY.load(Ordering::Relaxed), level)
+    }
+    fn shows(verbosity: u8, level: Level) -> bool {
+        (level as u8) < verbosity
+    }
+
+    #[macro_export]
+    macro_rules! log_info {
+        ($($arg:tt)*) => {
+            if $crate::log::enabled($crate::log::Level::Info) {
+                eprintln!($($arg)*);
+            }
+        };
+    }
+    #[macro_export]
+    macro_rules! log_debug {
+        ($($arg:tt)*) => {
+            if $crate::log::enabled($crate::log::Level::Debug) {
+                eprintln!($($arg)*);
+            }
+        };
+    }
+    #[macro_export]
+    macro_rules! log_trace {
+        ($($arg:tt)*) => {
+            if $crate::log::enabled($crate::log::Level::Trace) {
+                eprintln!($($arg)*);
+            }
+        };
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{shows, verbosity, Level};
+
+        fn shown(verbosity: u8) -> Vec<Level> {
+            [Level::Info, Level::Debug, Level::Trace]
+                .into_iter()
+                .filter(|&level| shows(verbosity, level))
+                .collect()
+        }
+
+        #[test]
+        fn flags() {
+            assert_eq!(shown(verbosity(0, true)), vec![]);
+            assert_eq!(shown(verbosity(0, false)), vec![Level::Info]);
+            assert_eq!(shown(verbosity(1, false)), vec![Level::Info, Level::Debug]);
+            assert_eq!(
+                shown(verbosity(2, false)),
+                vec![Level::Info, Level::Debug, Level::Trace]
+            );
+            assert_eq!(shown(verbosity(u8::MAX, false)).len(), 3);
+        }
+    }
+}
+
 pub mod math {
-    use std::num::NonZeroUsize;
+    use std::{fmt::Debug, ops::Div};
+
+    /// Primitive integer types supported by the generic functions
+    pub trait Integer: Copy + Ord + Debug + Div<Output = Self> {
+        const ZERO: Self;
+        const ONE: Self;
+        fn checked_mul(self, rhs: Self) -> Option<Self>;
+        /// Remainder, where the minimum signed value divided by -1 has remainder zero
+        fn wrapping_rem(self, rhs: Self) -> Self;
+        /// Absolute value, or `None` if not representable (the minimum signed value)
+        fn checked_abs(self) -> Option<Self>;
+        /// Widening conversion, or `None` if out of range (only `u128` values past `i128::MAX`)
+        fn to_i128(self) -> Option<i128>;
+        /// Narrowing conversion, or `None` if out of range
+        fn from_i128(value: i128) -> Option<Self>;
+    }
+
+    /// Signed [`Integer`] types, for results that may be negative
+    pub trait SignedInteger: Integer {}
+    macro_rules! impl_integer {
+        (unsigned: $($unsigned:ty),+; signed: $($signed:ty),+) => {
+            $(
+                impl Integer for $unsigned {
+                    const ZERO: Self = 0;
+                    const ONE: Self = 1;
+                    fn checked_mul(self, rhs: Self) -> Option<Self> {
+                        <$unsigned>::checked_mul(self, rhs)
+                    }
+                    fn wrapping_rem(self, rhs: Self) -> Self {
+                        <$unsigned>::wrapping_rem(self, rhs)
+                    }
+                    fn checked_abs(self) -> Option<Self> {
+                        Some(self)
+                    }
+                    fn to_i128(self) -> Option<i128> {
+                        i128::try_from(self).ok()
+                    }
+                    fn from_i128(value: i128) -> Option<Self> {
+                        <$unsigned>::try_from(value).ok()
+                    }
+                }
+            )+
+            $(
+                impl Integer for $signed {
+                    const ZERO: Self = 0;
+                    const ONE: Self = 1;
+                    fn checked_mul(self, rhs: Self) -> Option<Self> {
+                        <$signed>::checked_mul(self, rhs)
+                    }
+                    fn wrapping_rem(self, rhs: Self) -> Self {
+                        <$signed>::wrapping_rem(self, rhs)
+                    }
+                    fn checked_abs(self) -> Option<Self> {
+                        <$signed>::checked_abs(self)
+                    }
+                    fn to_i128(self) -> Option<i128> {
+                        i128::try_from(self).ok()
+                    }
+                    fn from_i128(value: i128) -> Option<Self> {
+                        <$signed>::try_from(value).ok()
+                    }
+                }
+                impl SignedInteger for $signed {}
+            )+
+        };
+    }
+    impl_integer!(unsigned: u8, u16, u32, u64, u128, usize; signed: i8, i16, i32, i64, i128, isize);
 
-    /// Greatest Common Divisor
+    /// Greatest Common Divisor, always nonnegative
+    ///
+    /// `gcd(0, 0)` is zero, the identity for [`gcd_all`] (every integer divides zero).
+    ///
+    /// Panics if the result is not representable, see [`checked_gcd`].
     ///
     /// ```
     /// use advent_2023::math::gcd;
     ///
     /// // trivial cases
-    /// assert_eq!(gcd(0, 3).get(), 3);
-    /// assert_eq!(gcd(2, 0).get(), 2);
-    /// assert_eq!(gcd(0, 0).get(), 1);
+    /// assert_eq!(gcd(0, 3), 3);
+    /// assert_eq!(gcd(2, 0), 2);
+    /// assert_eq!(gcd(0, 0), 0);
     ///
     /// // nontrivial
-    /// assert_eq!(gcd(2, 3).get(), 1, "2, 3");
-    /// assert_eq!(gcd(42, 6).get(), 6, "42, 6");
-    /// assert_eq!(gcd(6, 42).get(), 6, "6, 42");
-    /// assert_eq!(gcd(48, 18).get(), 6, "48, 18");
-    ///
+    /// assert_eq!(gcd(2, 3), 1, "2, 3");
+    /// assert_eq!(gcd(42, 6), 6, "42, 6");
+    /// assert_eq!(gcd(6, 42), 6, "6, 42");
+    /// assert_eq!(gcd(48_u32, 18), 6, "48, 18");
+    /// assert_eq!(gcd(-48_i64, 18), 6, "-48, 18");
+    /// assert_eq!(gcd(u128::MAX, 5), 5);
     /// ```
-    pub fn gcd(a: usize, b: usize) -> NonZeroUsize {
-        let one = NonZeroUsize::new(1).expect("nonzero");
-
-        let a = NonZeroUsize::try_from(a).ok();
-        let b = NonZeroUsize::try_from(b).ok();
-
-        match (a, b) {
-            (None, None) => one,
-            (Some(a), None) => a,
-            (None, Some(b)) => b,
-            (Some(a), Some(b)) => {
-                let a = a.get();
-                let b = b.get();
-
-                let (smaller, larger) = if a < b { (a, b) } else { (b, a) };
-                let larger = larger.rem_euclid(smaller);
+    pub fn gcd<T: Integer>(a: T, b: T) -> T {
+        checked_gcd(a, b).unwrap_or_else(|| panic!("gcd overflow for {a:?}, {b:?}"))
+    }
 
-                gcd(smaller, larger)
-            }
+    /// Greatest Common Divisor, or `None` if the result is not representable
+    ///
+    /// ```
+    /// use advent_2023::math::checked_gcd;
+    ///
+    /// assert_eq!(checked_gcd(i64::MIN, 6), Some(2));
+    /// assert_eq!(checked_gcd(i64::MIN, -1), Some(1));
+    /// assert_eq!(checked_gcd(i64::MIN, 0), None);
+    /// assert_eq!(checked_gcd(i64::MIN, i64::MIN), None);
+    /// ```
+    pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+        let (mut a, mut b) = (a, b);
+        while b != T::ZERO {
+            (a, b) = (b, a.wrapping_rem(b));
         }
+        a.checked_abs()
     }
 
-    /// Least Common Multiple
+    /// Least Common Multiple, always nonnegative
+    ///
+    /// Panics if the result is not representable, see [`checked_lcm`].
     ///
     /// ```
     /// use advent_2023::math::lcm;
@@ -367,97 +1007,558 @@ pub mod math {
     /// assert_eq!(lcm(2, 4), 4);
     /// assert_eq!(lcm(10, 7), 70);
     /// assert_eq!(lcm(5, 7), 35);
+    /// assert_eq!(lcm(0, 7), 0);
+    /// assert_eq!(lcm(-4_i32, 6), 12);
     /// ```
-    pub fn lcm(a: usize, b: usize) -> usize {
-        let divisor = gcd(a, b);
-        (a * b) / divisor
+    pub fn lcm<T: Integer>(a: T, b: T) -> T {
+        checked_lcm(a, b).unwrap_or_else(|| panic!("lcm overflow for {a:?}, {b:?}"))
     }
-}
 
-pub mod nonempty {
-    /// `Vec` guaranteed to be non-empty
+    /// Least Common Multiple, or `None` if the result is not representable
     ///
-    /// # Usage:
+    /// Divides by the GCD before multiplying, so only a result that overflows is `None`.
     ///
-    /// Mutable slice operations are OK
+    /// ```
+    /// use advent_2023::math::checked_lcm;
     ///
+    /// // the product overflows, but the result does not
+    /// let big = 1_u64 << 40;
+    /// assert_eq!(checked_lcm(big * 3, big * 5), Some(big * 15));
+    /// assert_eq!(checked_lcm(u32::MAX, u32::MAX - 1), None);
     /// ```
-    /// use advent_2023::{nonempty::NonEmptyVec, vec_nonempty};
-    /// let mut compile_error = NonEmptyVec::new(vec![1, 2, 3]).unwrap();
+    pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+        if a == T::ZERO || b == T::ZERO {
+            return Some(T::ZERO);
+        }
+        let divisor = checked_gcd(a, b)?;
+        (a / divisor).checked_abs()?.checked_mul(b.checked_abs()?)
+    }
+
+    /// Greatest Common Divisor of all values (zero if empty), or `None` if not representable
     ///
-    /// // slice mutation - allowed
-    /// compile_error[0] = 5;
+    /// ```
+    /// use advent_2023::math::gcd_all;
     ///
-    /// assert_eq!(compile_error, vec_nonempty![5, 2, 3]);
+    /// assert_eq!(gcd_all([12_u32, 18, 30]), Some(6));
+    /// assert_eq!(gcd_all(Vec::<i64>::new()), Some(0));
     /// ```
+    pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+        values.into_iter().try_fold(T::ZERO, checked_gcd)
+    }
+
+    /// Least Common Multiple of all values (one if empty), or `None` if not representable
     ///
-    /// Using mutable `Vec` methods is forbidden (slice mutation shown above is fine)
-    /// ```compile_fail
-    /// # use advent_2023::{nonempty::NonEmptyVec, vec_nonempty};
-    /// # let mut compile_error = NonEmptyVec::new(vec![1, 2, 3]).unwrap();
-    /// #
-    /// # // slice mutation - allowed
-    /// # compile_error[0] = 5;
-    /// #
-    /// # assert_eq!(compile_error, vec_nonempty![5, 2, 3]);
-    /// // Vec mutation - disallowed
-    /// compile_error.remove(0);
     /// ```
-    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    pub struct NonEmptyVec<T>(Vec<T>);
+    /// use advent_2023::math::lcm_all;
+    ///
+    /// assert_eq!(lcm_all([2_u64, 3, 4, 5]), Some(60));
+    /// assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+    /// assert_eq!(lcm_all([1_u8 << 7, 3]), None);
+    /// ```
+    pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+        values.into_iter().try_fold(T::ONE, checked_lcm)
+    }
 
-    impl<T> NonEmptyVec<T> {
-        pub fn new(inner: Vec<T>) -> Option<Self> {
-            if inner.is_empty() {
-                None
-            } else {
-                Some(Self(inner))
-            }
+    /// Extended Euclidean algorithm, returning `(g, x, y)` where `a*x + b*y == g == gcd(a, b)`
+    ///
+    /// The GCD is nonnegative, and zero only if both inputs are zero. Only signed types are
+    /// supported, as the coefficients may be negative. Panics if a result is not representable
+    /// (the GCD of two minimum values, such as `2^63` for `i64`).
+    ///
+    /// ```
+    /// use advent_2023::math::extended_gcd;
+    ///
+    /// assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+    /// assert_eq!(extended_gcd(0, -5), (5, 0, -1));
+    /// assert_eq!(extended_gcd(0, 0), (0, 0, 0));
+    /// assert_eq!(extended_gcd(12_i8, -18), (6, -1, -1));
+    /// ```
+    pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> (T, T, T) {
+        let wide = |n: T| n.to_i128().expect("signed integers fit in i128");
+        // NOTE: coefficients are bounded by the inputs, so only the GCD can be out of range
+        extended_gcd_wide(wide(a), wide(b))
+            .and_then(|(g, x, y)| Some((T::from_i128(g)?, T::from_i128(x)?, T::from_i128(y)?)))
+            .unwrap_or_else(|| panic!("extended GCD out of range for {a:?}, {b:?}"))
+    }
+    fn extended_gcd_wide(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+        let (mut old_r, mut r) = (a, b);
+        let (mut old_x, mut x) = (1_i128, 0_i128);
+        let (mut old_y, mut y) = (0_i128, 1_i128);
+        while r != 0 {
+            let quotient = old_r.checked_div(r)?;
+            (old_r, r) = (r, old_r.checked_sub(quotient.checked_mul(r)?)?);
+            (old_x, x) = (x, old_x.checked_sub(quotient.checked_mul(x)?)?);
+            (old_y, y) = (y, old_y.checked_sub(quotient.checked_mul(y)?)?);
         }
-
-        pub fn first(&self) -> &T {
-            self.0.first().expect("nonempty")
+        if old_r < 0 {
+            Some((
+                old_r.checked_neg()?,
+                old_x.checked_neg()?,
+                old_y.checked_neg()?,
+            ))
+        } else if old_r == 0 {
+            Some((0, 0, 0))
+        } else {
+            Some((old_r, old_x, old_y))
         }
+    }
 
-        pub fn split_first(&self) -> (&T, &[T]) {
-            self.0.split_first().expect("nonempty")
+    /// Modular inverse, the value in `0..modulus` where `a * inverse` is 1 (mod `modulus`)
+    ///
+    /// Returns `None` if `a` and `modulus` are not coprime, if `modulus` is not positive, or
+    /// if a value does not fit in `i128` (only possible for `u128`).
+    ///
+    /// ```
+    /// use advent_2023::math::mod_inverse;
+    ///
+    /// assert_eq!(mod_inverse(3, 11), Some(4));
+    /// assert_eq!(mod_inverse(-3, 11), Some(7));
+    /// assert_eq!(mod_inverse(6, 9), None);
+    /// assert_eq!(mod_inverse(5, 1), Some(0));
+    /// assert_eq!(mod_inverse(3_u64, u64::MAX - 1), Some(6148914691236517205));
+    /// ```
+    pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+        let (a, modulus) = (a.to_i128()?, modulus.to_i128()?);
+        if modulus <= 0 {
+            return None;
         }
+        let (g, x, _) = extended_gcd_wide(a.rem_euclid(modulus), modulus)?;
+        (g == 1).then(|| T::from_i128(x.rem_euclid(modulus)))?
+    }
 
-        pub fn last(&self) -> &T {
-            self.0.last().expect("nonempty")
+    /// Chinese Remainder Theorem, for congruences `x = offset (mod period)`
+    ///
+    /// Returns the smallest nonnegative solution `x` with the combined period (the LCM of all
+    /// periods), or `None` if the congruences contradict each other, a period is not positive,
+    /// or the combined period overflows. Periods need not be coprime; offsets may be negative.
+    /// Values are combined in `i128`, so `u128` values past `i128::MAX` also give `None`.
+    ///
+    /// ```
+    /// use advent_2023::math::crt;
+    ///
+    /// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    /// // shared factors are allowed when the offsets agree
+    /// assert_eq!(crt([(2, 4), (0, 6)]), Some((6, 12)));
+    /// assert_eq!(crt([(1, 4), (0, 6)]), None);
+    /// assert_eq!(crt([]), Some((0, 1)));
+    /// assert_eq!(crt([(-1_i64, 4), (1, 6)]), Some((7, 12)));
+    /// assert_eq!(crt([(0_u8, 16), (1, 17)]), None, "period 272 overflows u8");
+    /// assert_eq!(crt([(1, 3), (0, 0)]), None, "zero period");
+    /// ```
+    pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+        congruences
+            .into_iter()
+            .try_fold((T::ZERO, T::ONE), |current, next| crt_pair(current, next))
+    }
+    fn crt_pair<T: Integer>((a1, m1): (T, T), (a2, m2): (T, T)) -> Option<(T, T)> {
+        let (a1, m1, a2, m2) = (a1.to_i128()?, m1.to_i128()?, a2.to_i128()?, m2.to_i128()?);
+        if m1 <= 0 || m2 <= 0 {
+            return None;
         }
-
-        pub fn into_split_last(mut self) -> (Vec<T>, T) {
-            let last = self.0.pop().expect("nonempty");
-            (self.0, last)
+        let (a1, a2) = (a1.rem_euclid(m1), a2.rem_euclid(m2));
+        // m1 * inverse = g (mod m2)
+        let (g, inverse, _) = extended_gcd_wide(m1, m2)?;
+        let difference = a2 - a1;
+        if difference % g != 0 {
+            return None;
         }
+        let reduced_m2 = m2 / g;
+        // solve m1 * k = difference (mod m2), for the multiple of m1 to add to a1
+        let k = (difference / g % reduced_m2)
+            .checked_mul(inverse % reduced_m2)?
+            .rem_euclid(reduced_m2);
+        let period = m1.checked_mul(reduced_m2)?;
+        let offset = m1.checked_mul(k)?.checked_add(a1)?.rem_euclid(period);
+        Some((T::from_i128(offset)?, T::from_i128(period)?))
+    }
 
-        pub fn map<U>(self, map_fn: impl Fn(T) -> U) -> NonEmptyVec<U> {
-            let vec = self.0.into_iter().map(map_fn).collect();
-            NonEmptyVec::new(vec).expect("nonempty after map")
+    /// Integer square root, the largest `r` where `r * r <= n`
+    ///
+    /// ```
+    /// use advent_2023::math::isqrt;
+    ///
+    /// assert_eq!(isqrt(0), 0);
+    /// assert_eq!(isqrt(15), 3);
+    /// assert_eq!(isqrt(16), 4);
+    /// assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+    /// ```
+    pub fn isqrt(n: u128) -> u128 {
+        if n < 2 {
+            return n;
         }
-    }
-    // NOTE: Mutable access ONLY allowed as a slice
-    impl<T> std::ops::Deref for NonEmptyVec<T> {
-        type Target = [T]; // explicitly *NOT* type Target = Vec<T>;
-        fn deref(&self) -> &Self::Target {
-            &self.0[..]
+        // Newton's method, from an initial guess at or above the root
+        let bits = 128 - n.leading_zeros();
+        let mut root = 1 << bits.div_ceil(2);
+        loop {
+            let next = (root + n / root) / 2;
+            if next >= root {
+                return root;
+            }
+            root = next;
         }
     }
-    impl<T> std::ops::DerefMut for NonEmptyVec<T> {
-        fn deref_mut(&mut self) -> &mut Self::Target {
-            &mut self.0[..]
+
+    /// Counts the integers `t` in `0..=total` where `t * (total - t) > threshold`
+    ///
+    /// Writing `total = 2h + r` (with `r` 0 or 1), the product at `t = h - k` is
+    /// `h(h+r) - k(k+r)`, so the solutions are the `k` with `k(k+r) < h(h+r) - threshold`,
+    /// mirrored around the peak. The largest `k` comes from the square root, corrected for
+    /// rounding.
+    ///
+    /// ```
+    /// use advent_2023::math::count_products_exceeding;
+    ///
+    /// // 7 = 2 + 5 = 3 + 4 = ... where 2*5, 3*4, 4*3 and 5*2 exceed 9
+    /// assert_eq!(count_products_exceeding(7, 9), 4);
+    /// assert_eq!(count_products_exceeding(6, 8), 1);
+    /// assert_eq!(count_products_exceeding(6, 9), 0);
+    /// assert_eq!(count_products_exceeding(u128::MAX, 0), u128::MAX - 1);
+    /// ```
+    pub fn count_products_exceeding(total: u128, threshold: u128) -> u128 {
+        let (half, odd) = (total / 2, total % 2);
+        let Some(peak) = half.checked_mul(half + odd) else {
+            // the peak does not fit, but the count does
+            return count_products_exceeding_search(total, threshold);
+        };
+        let margin = match peak.checked_sub(threshold) {
+            Some(margin) if margin > 0 => margin,
+            _ => return 0,
+        };
+        let fits = |k: u128| {
+            k.checked_mul(k + odd)
+                .is_some_and(|product| product < margin)
+        };
+        let mut k = isqrt(margin);
+        while !fits(k) {
+            k -= 1;
         }
-    }
-    impl<T> std::iter::IntoIterator for NonEmptyVec<T> {
-        type Item = T;
-        type IntoIter = <Vec<T> as std::iter::IntoIterator>::IntoIter;
-        fn into_iter(self) -> Self::IntoIter {
-            self.0.into_iter()
+        while fits(k + 1) {
+            k += 1;
         }
+        2 * k + 1 + odd
     }
-
-    impl<T: std::fmt::Debug> std::fmt::Debug for NonEmptyVec<T> {
+    /// Binary search for the widest solution, comparing products without overflow
+    fn count_products_exceeding_search(total: u128, threshold: u128) -> u128 {
+        // `t * (total - t) > threshold`, as `(total - t) > threshold / t` for positive `t`
+        let exceeds = |t: u128| t > 0 && total - t > threshold / t;
+        let (half, odd) = (total / 2, total % 2);
+        if !exceeds(half) {
+            return 0;
+        }
+        // largest k where `half - k` exceeds, in low..high
+        let (mut low, mut high) = (0, half);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if exceeds(half - mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        2 * low + 1 + odd
+    }
+
+    /// Exact fraction in lowest terms, with a positive denominator
+    ///
+    /// ```
+    /// use advent_2023::math::Rational;
+    ///
+    /// let half = Rational::new(2, -4);
+    /// assert_eq!((half.numer(), half.denom()), (-1, 2));
+    /// assert_eq!(half + Rational::from(1), Rational::new(1, 2));
+    /// assert_eq!((half * Rational::from(-4)).to_integer(), Some(2));
+    /// assert_eq!(half.to_string(), "-1/2");
+    /// ```
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Rational {
+        numer: i128,
+        denom: i128,
+    }
+    impl Rational {
+        pub const ZERO: Self = Self { numer: 0, denom: 1 };
+
+        /// Panics if the denominator is zero or the fraction is not representable, see
+        /// [`Rational::checked_new`]
+        pub fn new(numer: i128, denom: i128) -> Self {
+            assert_ne!(denom, 0, "zero denominator for {numer}");
+            Self::checked_new(numer, denom)
+                .unwrap_or_else(|| panic!("{numer}/{denom} not representable"))
+        }
+        /// Fraction in lowest terms, or `None` if the denominator is zero or the fraction is
+        /// not representable (a negative denominator with `i128::MIN` left after reducing)
+        ///
+        /// ```
+        /// use advent_2023::math::Rational;
+        ///
+        /// assert_eq!(Rational::checked_new(i128::MIN, 2), Some(Rational::from(i128::MIN / 2)));
+        /// assert_eq!(Rational::checked_new(i128::MIN, -1), None);
+        /// assert_eq!(Rational::checked_new(1, 0), None);
+        /// ```
+        pub fn checked_new(numer: i128, denom: i128) -> Option<Self> {
+            if denom == 0 {
+                return None;
+            }
+            let divisor = checked_gcd(numer, denom)?;
+            let (numer, denom) = (numer / divisor, denom / divisor);
+            if denom < 0 {
+                Some(Self {
+                    numer: numer.checked_neg()?,
+                    denom: denom.checked_neg()?,
+                })
+            } else {
+                Some(Self { numer, denom })
+            }
+        }
+        pub fn numer(self) -> i128 {
+            self.numer
+        }
+        pub fn denom(self) -> i128 {
+            self.denom
+        }
+        pub fn is_integer(self) -> bool {
+            self.denom == 1
+        }
+        pub fn to_integer(self) -> Option<i128> {
+            self.is_integer().then_some(self.numer)
+        }
+        /// Sum, or `None` on overflow
+        pub fn checked_add(self, rhs: Self) -> Option<Self> {
+            let denom = checked_lcm(self.denom, rhs.denom)?;
+            let numer = (self.numer.checked_mul(denom / self.denom)?)
+                .checked_add(rhs.numer.checked_mul(denom / rhs.denom)?)?;
+            Self::checked_new(numer, denom)
+        }
+        /// Product, or `None` on overflow
+        pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+            // cross-reduce first, to keep the products small
+            let a = gcd(self.numer, rhs.denom).max(1);
+            let b = gcd(rhs.numer, self.denom).max(1);
+            Self::checked_new(
+                (self.numer / a).checked_mul(rhs.numer / b)?,
+                (self.denom / b).checked_mul(rhs.denom / a)?,
+            )
+        }
+    }
+    impl From<i128> for Rational {
+        fn from(value: i128) -> Self {
+            Self {
+                numer: value,
+                denom: 1,
+            }
+        }
+    }
+    impl std::ops::Add for Rational {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            self.checked_add(rhs)
+                .unwrap_or_else(|| panic!("overflow adding {self} and {rhs}"))
+        }
+    }
+    impl std::ops::Mul for Rational {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self {
+            self.checked_mul(rhs)
+                .unwrap_or_else(|| panic!("overflow multiplying {self} and {rhs}"))
+        }
+    }
+    impl std::fmt::Display for Rational {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let Self { numer, denom } = *self;
+            if denom == 1 {
+                write!(f, "{numer}")
+            } else {
+                write!(f, "{numer}/{denom}")
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{
+            checked_gcd, checked_lcm, count_products_exceeding, count_products_exceeding_search,
+            crt, extended_gcd, gcd, gcd_all, isqrt, lcm, lcm_all,
+        };
+        use proptest::prelude::*;
+
+        #[test]
+        fn crt_rejects_non_positive_periods() {
+            assert_eq!(crt([(0_u64, 0)]), None);
+            assert_eq!(crt([(2_i32, 3), (1, -5)]), None);
+            assert_eq!(crt([(1_i64, 0), (2, 3)]), None);
+        }
+
+        #[test]
+        fn gcd_of_zeros() {
+            // NOTE: previously 1, when the result was a `NonZeroUsize`
+            assert_eq!(gcd(0_usize, 0), 0);
+            assert_eq!(checked_gcd(0_i64, 0), Some(0));
+            assert_eq!(gcd_all([0_u32, 0]), Some(0));
+            assert_eq!(lcm(0_u64, 0), 0);
+        }
+
+        proptest! {
+            #[test]
+            fn isqrt_bounds(n in any::<u128>()) {
+                let root = isqrt(n);
+                prop_assert!(root * root <= n);
+                prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n));
+            }
+            #[test]
+            fn products_match_brute_force(total in 0..300_u128, threshold in 0..25_000_u128) {
+                let expected = (0..=total).filter(|t| t * (total - t) > threshold).count();
+                prop_assert_eq!(count_products_exceeding(total, threshold), expected as u128);
+                prop_assert_eq!(count_products_exceeding_search(total, threshold), expected as u128);
+            }
+            #[test]
+            fn products_match_search(total in any::<u64>(), fraction in 0.0..=1.0_f64) {
+                // thresholds near the boundary, at the product for some `t`
+                let total = u128::from(total);
+                let t = ((total as f64 * fraction) as u128).min(total);
+                let threshold = t * (total - t);
+                prop_assert_eq!(
+                    count_products_exceeding(total, threshold),
+                    count_products_exceeding_search(total, threshold)
+                );
+            }
+            #[test]
+            fn lcm_matches_wide(a in any::<u32>(), b in any::<u32>()) {
+                let divisor = gcd(a, b);
+                if divisor != 0 {
+                    prop_assert_eq!(a % divisor, 0);
+                    prop_assert_eq!(b % divisor, 0);
+                }
+                let wide = if divisor == 0 { 0 } else { u64::from(a) * u64::from(b) / u64::from(divisor) };
+                prop_assert_eq!(checked_lcm(a, b), u32::try_from(wide).ok());
+                prop_assert_eq!(checked_lcm(u64::from(a), u64::from(b)), Some(wide));
+            }
+            #[test]
+            fn signed_matches_unsigned(a in any::<i64>(), b in any::<i64>()) {
+                let unsigned = gcd(a.unsigned_abs(), b.unsigned_abs());
+                prop_assert_eq!(checked_gcd(a, b), i64::try_from(unsigned).ok());
+            }
+            #[test]
+            fn extended_gcd_bezout(a in any::<i64>(), b in any::<i64>()) {
+                prop_assume!(a != i64::MIN || b != i64::MIN);
+                let (g, x, y) = extended_gcd(a, b);
+                prop_assert_eq!(Some(g), checked_gcd(a, b));
+                let combination = i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y);
+                prop_assert_eq!(combination, i128::from(g));
+            }
+            #[test]
+            fn crt_solves(congruences in prop::collection::vec((any::<u16>(), 1..40_u64), 0..4)) {
+                let congruences: Vec<_> = congruences
+                    .into_iter()
+                    .map(|(offset, period)| (u64::from(offset), period))
+                    .collect();
+                let periods = congruences.iter().map(|&(_, period)| period);
+                match crt(congruences.iter().copied()) {
+                    Some((solution, period)) => {
+                        prop_assert_eq!(Some(period), lcm_all(periods));
+                        prop_assert!(solution < period);
+                        for &(offset, modulus) in &congruences {
+                            prop_assert_eq!(solution % modulus, offset % modulus);
+                        }
+                    }
+                    None => {
+                        // brute force over the combined period
+                        let period = lcm_all(periods).expect("small periods");
+                        let solution = (0..period).find(|solution| {
+                            congruences
+                                .iter()
+                                .all(|&(offset, modulus)| solution % modulus == offset % modulus)
+                        });
+                        prop_assert_eq!(solution, None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub mod nonempty {
+    /// `Vec` guaranteed to be non-empty
+    ///
+    /// # Usage:
+    ///
+    /// Mutable slice operations are OK
+    ///
+    /// ```
+    /// use advent_2023::{nonempty::NonEmptyVec, vec_nonempty};
+    /// let mut compile_error = NonEmptyVec::new(vec![1, 2, 3]).unwrap();
+    ///
+    /// // slice mutation - allowed
+    /// compile_error[0] = 5;
+    ///
+    /// assert_eq!(compile_error, vec_nonempty![5, 2, 3]);
+    /// ```
+    ///
+    /// Using mutable `Vec` methods is forbidden (slice mutation shown above is fine)
+    /// ```compile_fail
+    /// # use advent_2023::{nonempty::NonEmptyVec, vec_nonempty};
+    /// # let mut compile_error = NonEmptyVec::new(vec![1, 2, 3]).unwrap();
+    /// #
+    /// # // slice mutation - allowed
+    /// # compile_error[0] = 5;
+    /// #
+    /// # assert_eq!(compile_error, vec_nonempty![5, 2, 3]);
+    /// // Vec mutation - disallowed
+    /// compile_error.remove(0);
+    /// ```
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct NonEmptyVec<T>(Vec<T>);
+
+    impl<T> NonEmptyVec<T> {
+        pub fn new(inner: Vec<T>) -> Option<Self> {
+            if inner.is_empty() {
+                None
+            } else {
+                Some(Self(inner))
+            }
+        }
+
+        pub fn first(&self) -> &T {
+            self.0.first().expect("nonempty")
+        }
+
+        pub fn split_first(&self) -> (&T, &[T]) {
+            self.0.split_first().expect("nonempty")
+        }
+
+        pub fn last(&self) -> &T {
+            self.0.last().expect("nonempty")
+        }
+
+        pub fn into_split_last(mut self) -> (Vec<T>, T) {
+            let last = self.0.pop().expect("nonempty");
+            (self.0, last)
+        }
+
+        pub fn map<U>(self, map_fn: impl Fn(T) -> U) -> NonEmptyVec<U> {
+            let vec = self.0.into_iter().map(map_fn).collect();
+            NonEmptyVec::new(vec).expect("nonempty after map")
+        }
+    }
+    // NOTE: Mutable access ONLY allowed as a slice
+    impl<T> std::ops::Deref for NonEmptyVec<T> {
+        type Target = [T]; // explicitly *NOT* type Target = Vec<T>;
+        fn deref(&self) -> &Self::Target {
+            &self.0[..]
+        }
+    }
+    impl<T> std::ops::DerefMut for NonEmptyVec<T> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0[..]
+        }
+    }
+    impl<T> std::iter::IntoIterator for NonEmptyVec<T> {
+        type Item = T;
+        type IntoIter = <Vec<T> as std::iter::IntoIterator>::IntoIter;
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.into_iter()
+        }
+    }
+
+    impl<T: std::fmt::Debug> std::fmt::Debug for NonEmptyVec<T> {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             <Vec<T> as std::fmt::Debug>::fmt(&self.0, f)
         }
@@ -502,7 +1603,7 @@ pub mod either {
 }
 
 pub mod point {
-    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Point {
         pub row: usize,
         pub col: usize,
@@ -530,6 +1631,127 @@ pub mod point {
             }
         }
     }
+    impl Point {
+        /// <https://en.wikipedia.org/wiki/Taxicab_geometry>
+        pub fn manhattan_distance(self, other: Self) -> usize {
+            self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+        }
+    }
+
+    /// Point that may extend into negative space (e.g. off the top or left of a grid)
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct SignedPoint {
+        pub row: i64,
+        pub col: i64,
+    }
+    impl SignedPoint {
+        pub const ORIGIN: Self = Self { row: 0, col: 0 };
+        /// <https://en.wikipedia.org/wiki/Taxicab_geometry>
+        pub fn manhattan_distance(self, other: Self) -> u64 {
+            self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+        }
+    }
+    impl std::ops::Add for SignedPoint {
+        type Output = Self;
+        fn add(self, other: Self) -> Self::Output {
+            Self {
+                row: self.row + other.row,
+                col: self.col + other.col,
+            }
+        }
+    }
+    impl std::ops::Sub for SignedPoint {
+        type Output = Self;
+        fn sub(self, other: Self) -> Self::Output {
+            Self {
+                row: self.row - other.row,
+                col: self.col - other.col,
+            }
+        }
+    }
+    impl std::ops::Neg for SignedPoint {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            Self {
+                row: -self.row,
+                col: -self.col,
+            }
+        }
+    }
+    impl std::ops::Mul<i64> for SignedPoint {
+        type Output = Self;
+        fn mul(self, factor: i64) -> Self::Output {
+            Self {
+                row: self.row * factor,
+                col: self.col * factor,
+            }
+        }
+    }
+    impl std::ops::AddAssign for SignedPoint {
+        fn add_assign(&mut self, other: Self) {
+            *self = *self + other;
+        }
+    }
+    impl std::ops::SubAssign for SignedPoint {
+        fn sub_assign(&mut self, other: Self) {
+            *self = *self - other;
+        }
+    }
+    impl TryFrom<Point> for SignedPoint {
+        type Error = std::num::TryFromIntError;
+        fn try_from(point: Point) -> Result<Self, Self::Error> {
+            Ok(Self {
+                row: point.row.try_into()?,
+                col: point.col.try_into()?,
+            })
+        }
+    }
+    /// Fails for points in negative space
+    impl TryFrom<SignedPoint> for Point {
+        type Error = std::num::TryFromIntError;
+        fn try_from(point: SignedPoint) -> Result<Self, Self::Error> {
+            Ok(Self {
+                row: point.row.try_into()?,
+                col: point.col.try_into()?,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Point, SignedPoint};
+        use crate::direction;
+
+        #[test]
+        fn signed_ops() {
+            let a = SignedPoint { row: 2, col: -3 };
+            let b = SignedPoint { row: -1, col: 5 };
+            assert_eq!(a + b, SignedPoint { row: 1, col: 2 });
+            assert_eq!(a - b, SignedPoint { row: 3, col: -8 });
+            assert_eq!(-a, SignedPoint { row: -2, col: 3 });
+            assert_eq!(a * 3, SignedPoint { row: 6, col: -9 });
+            assert_eq!(a.manhattan_distance(b), 3 + 8);
+            assert_eq!(a.manhattan_distance(a), 0);
+
+            let mut moved = SignedPoint::ORIGIN;
+            moved += direction::NORTH.offset() * 2;
+            moved -= direction::WEST.offset();
+            assert_eq!(moved, SignedPoint { row: -2, col: 1 });
+        }
+        #[test]
+        fn conversions() {
+            let point = Point { row: 4, col: 7 };
+            let signed = SignedPoint::try_from(point).unwrap();
+            assert_eq!(signed, SignedPoint { row: 4, col: 7 });
+            assert_eq!(Point::try_from(signed), Ok(point));
+            assert!(Point::try_from(SignedPoint { row: -1, col: 0 }).is_err());
+            assert!(SignedPoint::try_from(Point {
+                row: usize::MAX,
+                col: 0
+            })
+            .is_err());
+        }
+    }
 }
 
 pub mod dimension {
@@ -570,24 +1792,24 @@ pub mod dimension {
 }
 
 pub mod direction {
-    use super::point::Point;
+    use super::point::{Point, SignedPoint};
 
     pub const NORTH: Direction = Direction::V(DirectionV::North);
     pub const SOUTH: Direction = Direction::V(DirectionV::South);
     pub const EAST: Direction = Direction::H(DirectionH::East);
     pub const WEST: Direction = Direction::H(DirectionH::West);
 
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub enum Direction {
         V(DirectionV),
         H(DirectionH),
     }
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub enum DirectionV {
         North,
         South,
     }
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub enum DirectionH {
         East,
         West,
@@ -610,6 +1832,45 @@ pub mod direction {
 
             row.zip(col).map(|(row, col)| Point { row, col })
         }
+        /// Ordinal position within [`Self::ALL`]
+        pub const fn index(self) -> usize {
+            match self {
+                Self::V(DirectionV::North) => 0,
+                Self::V(DirectionV::South) => 1,
+                Self::H(DirectionH::East) => 2,
+                Self::H(DirectionH::West) => 3,
+            }
+        }
+        /// Turns 90 degrees counterclockwise (e.g. north to west)
+        pub fn turn_left(self) -> Self {
+            match self {
+                NORTH => WEST,
+                WEST => SOUTH,
+                SOUTH => EAST,
+                EAST => NORTH,
+            }
+        }
+        /// Turns 90 degrees clockwise (e.g. north to east)
+        pub fn turn_right(self) -> Self {
+            -self.turn_left()
+        }
+        /// Returns the outgoing direction after traveling in this direction into the mirror
+        pub fn reflect(self, mirror: Mirror) -> Self {
+            match (mirror, self) {
+                (Mirror::NwToSe, Self::V(_)) | (Mirror::SwToNe, Self::H(_)) => self.turn_left(),
+                (Mirror::NwToSe, Self::H(_)) | (Mirror::SwToNe, Self::V(_)) => self.turn_right(),
+            }
+        }
+        /// Returns the unit step in this direction, for moving without bounds at zero
+        pub fn offset(self) -> SignedPoint {
+            let (row, col) = match self {
+                Self::V(DirectionV::North) => (-1, 0),
+                Self::V(DirectionV::South) => (1, 0),
+                Self::H(DirectionH::East) => (0, 1),
+                Self::H(DirectionH::West) => (0, -1),
+            };
+            SignedPoint { row, col }
+        }
     }
     impl std::ops::Neg for Direction {
         type Output = Self;
@@ -638,4 +1899,991 @@ pub mod direction {
             }
         }
     }
+
+    /// Diagonal mirror, which turns a [`Direction`] by 90 degrees
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Mirror {
+        /// `\`
+        NwToSe,
+        /// `/`
+        SwToNe,
+    }
+
+    /// Value for each of the four [`Direction`]s
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct DirectionMap<T>([Option<T>; 4]);
+    impl<T> DirectionMap<T> {
+        /// Returns the previous value, if any
+        pub fn insert(&mut self, direction: Direction, value: T) -> Option<T> {
+            self.0[direction.index()].replace(value)
+        }
+        pub fn remove(&mut self, direction: Direction) -> Option<T> {
+            self.0[direction.index()].take()
+        }
+        pub fn get(&self, direction: Direction) -> Option<&T> {
+            self.0[direction.index()].as_ref()
+        }
+        pub fn get_mut(&mut self, direction: Direction) -> Option<&mut T> {
+            self.0[direction.index()].as_mut()
+        }
+        pub fn contains(&self, direction: Direction) -> bool {
+            self.get(direction).is_some()
+        }
+        /// Returns each direction and value, in the order of [`Direction::ALL`]
+        pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+            Direction::ALL
+                .iter()
+                .zip(&self.0)
+                .filter_map(|(&direction, value)| Some((direction, value.as_ref()?)))
+        }
+    }
+    impl<T> Default for DirectionMap<T> {
+        fn default() -> Self {
+            Self([None, None, None, None])
+        }
+    }
+
+    /// Set of [`Direction`]s, stored as bits
+    #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct DirectionSet(u8);
+    impl DirectionSet {
+        fn bit(direction: Direction) -> u8 {
+            1 << direction.index()
+        }
+        /// Returns `true` if the direction was not already present
+        pub fn insert(&mut self, direction: Direction) -> bool {
+            let added = !self.contains(direction);
+            self.0 |= Self::bit(direction);
+            added
+        }
+        /// Returns `true` if the direction was present
+        pub fn remove(&mut self, direction: Direction) -> bool {
+            let removed = self.contains(direction);
+            self.0 &= !Self::bit(direction);
+            removed
+        }
+        pub fn contains(self, direction: Direction) -> bool {
+            self.0 & Self::bit(direction) != 0
+        }
+        pub fn len(self) -> usize {
+            self.0.count_ones() as usize
+        }
+        pub fn is_empty(self) -> bool {
+            self.0 == 0
+        }
+        /// Returns each direction in the set, in the order of [`Direction::ALL`]
+        pub fn iter(self) -> impl Iterator<Item = Direction> {
+            Direction::ALL
+                .iter()
+                .copied()
+                .filter(move |&direction| self.contains(direction))
+        }
+    }
+    impl FromIterator<Direction> for DirectionSet {
+        fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+            let mut set = Self::default();
+            for direction in iter {
+                set.insert(direction);
+            }
+            set
+        }
+    }
+    impl std::fmt::Debug for DirectionSet {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+
+    /// One of the eight compass directions, including the diagonals
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Direction8 {
+        North,
+        NorthEast,
+        East,
+        SouthEast,
+        South,
+        SouthWest,
+        West,
+        NorthWest,
+    }
+    impl Direction8 {
+        /// All directions, clockwise starting from [`Self::North`]
+        pub const ALL: &'static [Self] = &[
+            Self::North,
+            Self::NorthEast,
+            Self::East,
+            Self::SouthEast,
+            Self::South,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+        ];
+        pub fn of(self, src: Point) -> Option<Point> {
+            let SignedPoint { row, col } = self.offset();
+            let row = src.row.checked_add_signed(row.try_into().ok()?)?;
+            let col = src.col.checked_add_signed(col.try_into().ok()?)?;
+            Some(Point { row, col })
+        }
+        /// Returns the unit step in this direction (diagonals step in both dimensions)
+        pub fn offset(self) -> SignedPoint {
+            let (row, col) = match self {
+                Self::North => (-1, 0),
+                Self::NorthEast => (-1, 1),
+                Self::East => (0, 1),
+                Self::SouthEast => (1, 1),
+                Self::South => (1, 0),
+                Self::SouthWest => (1, -1),
+                Self::West => (0, -1),
+                Self::NorthWest => (-1, -1),
+            };
+            SignedPoint { row, col }
+        }
+        pub fn is_diagonal(self) -> bool {
+            !self.index().is_multiple_of(2)
+        }
+        fn index(self) -> usize {
+            self as usize
+        }
+        /// Rotates clockwise by the number of 45 degree steps
+        fn rotate_steps(self, steps: usize) -> Self {
+            Self::ALL[(self.index() + steps) % Self::ALL.len()]
+        }
+        pub fn rotate_right_45(self) -> Self {
+            self.rotate_steps(1)
+        }
+        pub fn rotate_right_90(self) -> Self {
+            self.rotate_steps(2)
+        }
+        pub fn rotate_left_45(self) -> Self {
+            self.rotate_steps(7)
+        }
+        pub fn rotate_left_90(self) -> Self {
+            self.rotate_steps(6)
+        }
+    }
+    impl std::ops::Neg for Direction8 {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            self.rotate_steps(4)
+        }
+    }
+    impl From<Direction> for Direction8 {
+        fn from(direction: Direction) -> Self {
+            match direction {
+                NORTH => Self::North,
+                SOUTH => Self::South,
+                EAST => Self::East,
+                WEST => Self::West,
+            }
+        }
+    }
+    /// Fails for diagonal directions
+    impl TryFrom<Direction8> for Direction {
+        type Error = Direction8;
+        fn try_from(direction: Direction8) -> Result<Self, Self::Error> {
+            match direction {
+                Direction8::North => Ok(NORTH),
+                Direction8::South => Ok(SOUTH),
+                Direction8::East => Ok(EAST),
+                Direction8::West => Ok(WEST),
+                diagonal => Err(diagonal),
+            }
+        }
+    }
+
+    impl Point {
+        /// Returns the orthogonal neighbors (4-connectivity), in the order of [`Direction::ALL`]
+        pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+            Direction::ALL
+                .iter()
+                .filter_map(move |direction| direction.of(self))
+        }
+        /// Returns the orthogonal and diagonal neighbors (8-connectivity), in the order of
+        /// [`Direction8::ALL`]
+        pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+            Direction8::ALL
+                .iter()
+                .filter_map(move |direction| direction.of(self))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{
+            Direction, Direction8, DirectionMap, DirectionSet, Mirror, EAST, NORTH, SOUTH, WEST,
+        };
+        use crate::point::Point;
+
+        #[test]
+        fn turn() {
+            assert_eq!(NORTH.turn_right(), EAST);
+            assert_eq!(NORTH.turn_left(), WEST);
+            for &direction in Direction::ALL {
+                assert_eq!(direction.turn_left().turn_right(), direction);
+                assert_eq!(direction.turn_left().turn_left(), -direction);
+                assert_eq!(Direction::ALL[direction.index()], direction);
+                assert_eq!(
+                    Direction8::from(direction.turn_right()),
+                    Direction8::from(direction).rotate_right_90()
+                );
+                for mirror in [Mirror::NwToSe, Mirror::SwToNe] {
+                    let reflected = direction.reflect(mirror);
+                    assert_eq!(reflected.reflect(mirror), direction);
+                    assert_eq!((-reflected).reflect(mirror), -direction);
+                }
+            }
+            // `\` deflects a beam heading east down to the south
+            assert_eq!(EAST.reflect(Mirror::NwToSe), SOUTH);
+            assert_eq!(EAST.reflect(Mirror::SwToNe), NORTH);
+        }
+        #[test]
+        fn direction_map() {
+            let mut map = DirectionMap::default();
+            assert_eq!(map.insert(EAST, 'e'), None);
+            assert_eq!(map.insert(NORTH, 'n'), None);
+            assert_eq!(map.insert(EAST, 'E'), Some('e'));
+            assert_eq!(map.get(EAST), Some(&'E'));
+            assert!(!map.contains(WEST));
+            assert_eq!(
+                map.iter().collect::<Vec<_>>(),
+                vec![(NORTH, &'n'), (EAST, &'E')]
+            );
+            assert_eq!(map.remove(NORTH), Some('n'));
+            assert_eq!(map.get(NORTH), None);
+        }
+        #[test]
+        fn direction_set() {
+            let mut set = DirectionSet::default();
+            assert!(set.is_empty());
+            assert!(set.insert(WEST));
+            assert!(!set.insert(WEST));
+            assert!(set.insert(SOUTH));
+            assert_eq!(set.len(), 2);
+            assert_eq!(set.iter().collect::<Vec<_>>(), vec![SOUTH, WEST]);
+            assert!(set.remove(WEST));
+            assert!(!set.remove(WEST));
+            assert_eq!(set, [SOUTH].into_iter().collect());
+            assert_eq!(format!("{set:?}"), "{V(South)}");
+        }
+
+        #[test]
+        fn rotate() {
+            for &direction in Direction8::ALL {
+                let full_turn = (0..8).fold(direction, |d, _| d.rotate_right_45());
+                assert_eq!(full_turn, direction);
+                assert_eq!(direction.rotate_right_90().rotate_right_90(), -direction);
+                assert_eq!(direction.rotate_left_90(), -direction.rotate_right_90());
+                assert_eq!(direction.rotate_left_45().rotate_right_45(), direction);
+                assert_eq!(-direction.offset(), (-direction).offset());
+            }
+            assert_eq!(Direction8::North.rotate_right_45(), Direction8::NorthEast);
+            assert_eq!(Direction8::North.rotate_left_90(), Direction8::West);
+        }
+        #[test]
+        fn convert() {
+            for &direction in Direction::ALL {
+                let eight = Direction8::from(direction);
+                assert!(!eight.is_diagonal());
+                assert_eq!(Direction::try_from(eight), Ok(direction));
+                assert_eq!(eight.offset(), direction.offset());
+            }
+            assert_eq!(
+                Direction::try_from(Direction8::SouthWest),
+                Err(Direction8::SouthWest)
+            );
+            assert_eq!(Direction8::from(NORTH).rotate_right_90(), Direction8::East);
+        }
+        #[test]
+        fn neighbors() {
+            let origin = Point { row: 0, col: 0 };
+            assert_eq!(origin.neighbors4().count(), 2);
+            assert_eq!(
+                origin.neighbors8().collect::<Vec<_>>(),
+                vec![
+                    Point { row: 0, col: 1 },
+                    Point { row: 1, col: 1 },
+                    Point { row: 1, col: 0 },
+                ]
+            );
+            let center = Point { row: 1, col: 1 };
+            assert_eq!(center.neighbors4().count(), 4);
+            assert_eq!(center.neighbors8().count(), 8);
+            assert_eq!(Direction8::NorthWest.of(center), Some(origin));
+        }
+    }
+}
+
+pub mod search {
+    //! Graph searches over a user-supplied neighbor function
+    //!
+    //! Nodes can be any state, such as a [`Point`](crate::point::Point) in a grid, a
+    //! `(Point, Direction)` beam, or a key in a lookup table.
+
+    use std::{
+        collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+        hash::Hash,
+        ops::Add,
+    };
+
+    /// Shortest known distance to each reached node, with the previous node on that path
+    #[derive(Clone, Debug)]
+    pub struct Explored<N, C> {
+        nodes: HashMap<N, (C, Option<N>)>,
+    }
+    impl<N, C> Explored<N, C>
+    where
+        N: Clone + Eq + Hash,
+        C: Copy,
+    {
+        fn new(start: N, zero: C) -> Self {
+            Self {
+                nodes: HashMap::from([(start, (zero, None))]),
+            }
+        }
+        pub fn distance(&self, node: &N) -> Option<C> {
+            self.nodes.get(node).map(|&(distance, _)| distance)
+        }
+        /// Returns the nodes from the start to the specified node (inclusive), if reached
+        pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+            self.nodes.get(node)?;
+            let mut path = vec![node.clone()];
+            let mut current = node;
+            while let Some((_, Some(previous))) = self.nodes.get(current) {
+                path.push(previous.clone());
+                current = previous;
+            }
+            path.reverse();
+            Some(path)
+        }
+        /// Returns each reached node and its distance (in arbitrary order)
+        pub fn distances(&self) -> impl Iterator<Item = (&N, C)> {
+            self.nodes
+                .iter()
+                .map(|(node, &(distance, _))| (node, distance))
+        }
+        pub fn nodes(&self) -> impl Iterator<Item = &N> {
+            self.nodes.keys()
+        }
+        pub fn len(&self) -> usize {
+            self.nodes.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.nodes.is_empty()
+        }
+    }
+
+    /// Path found to a goal node, with the total cost
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Path<N, C> {
+        /// Nodes from the start to the goal (inclusive)
+        pub nodes: Vec<N>,
+        pub cost: C,
+    }
+
+    /// Breadth-first search, where each step costs 1
+    pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Explored<N, usize>
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        let mut explored = Explored::new(start.clone(), 0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let distance = explored.distance(&node).expect("queued nodes are explored") + 1;
+            for next in neighbors(&node) {
+                if let Entry::Vacant(entry) = explored.nodes.entry(next.clone()) {
+                    entry.insert((distance, Some(node.clone())));
+                    queue.push_back(next);
+                }
+            }
+        }
+        explored
+    }
+
+    /// Lowest-cost search from the start to all reachable nodes, for nonnegative step costs
+    pub fn dijkstra<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Explored<N, C>
+    where
+        N: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let (explored, _) = best_first(start, neighbors, |_| C::default(), |_| false);
+        explored
+    }
+
+    /// Lowest-cost path from the start to the first goal node
+    ///
+    /// The heuristic must never overestimate the remaining cost to a goal (e.g. the Manhattan
+    /// distance for unit steps on a grid), otherwise the path may not be the lowest cost.
+    pub fn astar<N, C, I>(
+        start: N,
+        neighbors: impl FnMut(&N) -> I,
+        heuristic: impl FnMut(&N) -> C,
+        is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<Path<N, C>>
+    where
+        N: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let (explored, goal) = best_first(start, neighbors, heuristic, is_goal);
+        let goal = goal?;
+        Some(Path {
+            cost: explored.distance(&goal).expect("goal is explored"),
+            nodes: explored.path_to(&goal).expect("goal is explored"),
+        })
+    }
+
+    fn best_first<N, C, I>(
+        start: N,
+        mut neighbors: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> (Explored<N, C>, Option<N>)
+    where
+        N: Clone + Eq + Hash,
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let zero = C::default();
+        let mut explored = Explored::new(start.clone(), zero);
+        let mut queue = BinaryHeap::from([Queued {
+            priority: heuristic(&start),
+            cost: zero,
+            node: start,
+        }]);
+        while let Some(Queued { cost, node, .. }) = queue.pop() {
+            if explored.distance(&node).is_some_and(|best| best < cost) {
+                // stale entry, already reached at a lower cost
+                continue;
+            }
+            if is_goal(&node) {
+                return (explored, Some(node));
+            }
+            for (next, step_cost) in neighbors(&node) {
+                let next_cost = cost + step_cost;
+                if explored
+                    .distance(&next)
+                    .is_some_and(|best| best <= next_cost)
+                {
+                    continue;
+                }
+                explored
+                    .nodes
+                    .insert(next.clone(), (next_cost, Some(node.clone())));
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+        (explored, None)
+    }
+
+    /// Entry in the priority queue, ordered so the lowest priority is popped first
+    struct Queued<N, C> {
+        priority: C,
+        cost: C,
+        node: N,
+    }
+    impl<N, C: Ord> PartialEq for Queued<N, C> {
+        fn eq(&self, other: &Self) -> bool {
+            self.priority == other.priority
+        }
+    }
+    impl<N, C: Ord> Eq for Queued<N, C> {}
+    impl<N, C: Ord> PartialOrd for Queued<N, C> {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl<N, C: Ord> Ord for Queued<N, C> {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            other.priority.cmp(&self.priority)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{astar, bfs, dijkstra, Path};
+        use crate::{grid::Grid, point::Point};
+
+        fn maze() -> Grid<bool> {
+            let input = "\
+..#....
+.##.##.
+....#..
+.#.##.#
+...#.#.";
+            Grid::parse(input, |c| Ok(c == '#'))
+                .unwrap()
+                .expect("nonempty")
+        }
+        fn open_neighbors(grid: &Grid<bool>, point: Point) -> Vec<Point> {
+            grid.neighbors(point)
+                .filter(|&(_, _, &wall)| !wall)
+                .map(|(_, neighbor, _)| neighbor)
+                .collect()
+        }
+
+        #[test]
+        fn bfs_grid() {
+            let grid = maze();
+            let start = Point { row: 0, col: 0 };
+            let explored = bfs(start, |&point| open_neighbors(&grid, point));
+            let end = Point { row: 0, col: 6 };
+            assert_eq!(explored.distance(&end), Some(10));
+            let path = explored.path_to(&end).unwrap();
+            assert_eq!(path.len(), 11);
+            assert_eq!((path.first(), path.last()), (Some(&start), Some(&end)));
+            assert!(path
+                .windows(2)
+                .all(|pair| pair[0].manhattan_distance(pair[1]) == 1));
+
+            // walled off
+            assert_eq!(explored.distance(&Point { row: 4, col: 4 }), None);
+            assert_eq!(explored.path_to(&Point { row: 0, col: 2 }), None);
+            assert_eq!(explored.distance(&start), Some(0));
+        }
+
+        #[test]
+        fn weighted() {
+            // a -1-> b -1-> c, and a -5-> c
+            let edges = |&node: &char| match node {
+                'a' => vec![('b', 1), ('c', 5)],
+                'b' => vec![('c', 1)],
+                _ => vec![],
+            };
+            let explored = dijkstra('a', edges);
+            assert_eq!(explored.distance(&'c'), Some(2));
+            assert_eq!(explored.path_to(&'c'), Some(vec!['a', 'b', 'c']));
+            assert_eq!(explored.len(), 3);
+
+            let path = astar('a', edges, |_| 0, |&node| node == 'c');
+            assert_eq!(
+                path,
+                Some(Path {
+                    nodes: vec!['a', 'b', 'c'],
+                    cost: 2
+                })
+            );
+            assert_eq!(astar('c', edges, |_| 0, |&node| node == 'a'), None);
+        }
+
+        #[test]
+        fn astar_matches_dijkstra() {
+            let grid = maze();
+            let start = Point { row: 0, col: 0 };
+            let unit_steps = |&point: &Point| {
+                open_neighbors(&grid, point)
+                    .into_iter()
+                    .map(|neighbor| (neighbor, 1))
+            };
+            let explored = dijkstra(start, unit_steps);
+            for (&goal, distance) in explored.distances() {
+                let path = astar(
+                    start,
+                    unit_steps,
+                    |point| point.manhattan_distance(goal),
+                    |&point| point == goal,
+                )
+                .unwrap();
+                assert_eq!(path.cost, distance, "{goal:?}");
+                assert_eq!(path.nodes.len(), distance + 1);
+            }
+        }
+    }
+}
+
+pub mod cycle {
+    //! Cycle detection for iterated state machines, to fast-forward through many steps
+    //!
+    //! The state must capture everything the step function depends on (e.g. a phase that
+    //! selects the next instruction), otherwise equal states may not repeat the same way.
+
+    use std::{collections::HashMap, hash::Hash};
+
+    /// Repetition found in the sequence of states, starting from the initial state (step 0)
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Cycle {
+        /// First step of the first repetition
+        pub start: usize,
+        /// Number of steps until a state repeats
+        pub length: usize,
+    }
+    impl Cycle {
+        /// Returns the earliest step with the same state as the specified step
+        pub fn reduce(self, step: usize) -> usize {
+            match step.checked_sub(self.start) {
+                Some(offset) => self.start + offset % self.length,
+                None => step,
+            }
+        }
+    }
+
+    /// Finds the cycle by storing each state, returning the states before the cycle repeats
+    ///
+    /// The returned states are indexed by step, so `states[cycle.reduce(n)]` is the state after
+    /// `n` steps. Does not terminate if the states never repeat.
+    pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>)
+    where
+        S: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut current = initial;
+        loop {
+            let index = states.len();
+            if let Some(&start) = seen.get(&current) {
+                let length = index - start;
+                return (Cycle { start, length }, states);
+            }
+            seen.insert(current.clone(), index);
+            let next = step(&current);
+            states.push(current);
+            current = next;
+        }
+    }
+
+    /// Finds the cycle using Brent's algorithm, storing only a constant number of states
+    ///
+    /// Useful when states are too large to store, at the cost of repeating some steps.
+    /// Does not terminate if the states never repeat.
+    pub fn find_cycle_brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle
+    where
+        S: Clone + Eq,
+    {
+        // find the length, comparing against checkpoints at increasing powers of two
+        let mut power = 1;
+        let mut length = 1;
+        let mut checkpoint = initial.clone();
+        let mut current = step(&initial);
+        while checkpoint != current {
+            if power == length {
+                checkpoint = current.clone();
+                power *= 2;
+                length = 0;
+            }
+            current = step(&current);
+            length += 1;
+        }
+
+        // find the start, walking two states spaced `length` apart until they meet
+        let mut behind = initial.clone();
+        let mut ahead = initial;
+        for _ in 0..length {
+            ahead = step(&ahead);
+        }
+        let mut start = 0;
+        while behind != ahead {
+            behind = step(&behind);
+            ahead = step(&ahead);
+            start += 1;
+        }
+        Cycle { start, length }
+    }
+
+    /// Returns the state after the specified number of steps, skipping repeated cycles
+    pub fn nth_state<S>(initial: S, count: usize, mut step: impl FnMut(&S) -> S) -> S
+    where
+        S: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut current = initial;
+        for index in 0..count {
+            if let Some(&start) = seen.get(&current) {
+                let cycle = Cycle {
+                    start,
+                    length: index - start,
+                };
+                crate::log_debug!("detected cycle at step {index}: {cycle:?}");
+                states.truncate(cycle.reduce(count) + 1);
+                return states.pop().expect("reduced step is stored");
+            }
+            seen.insert(current.clone(), index);
+            let next = step(&current);
+            states.push(current);
+            current = next;
+        }
+        current
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{find_cycle, find_cycle_brent, nth_state, Cycle};
+
+        /// 0, 1, 2, 3, 4, 5, 6, 3, 4, ...
+        fn step(&n: &u32) -> u32 {
+            if n == 6 {
+                3
+            } else {
+                n + 1
+            }
+        }
+
+        #[test]
+        fn finds_cycle() {
+            let expected = Cycle {
+                start: 3,
+                length: 4,
+            };
+            let (cycle, states) = find_cycle(0, step);
+            assert_eq!(cycle, expected);
+            assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6]);
+            assert_eq!(find_cycle_brent(0, step), expected);
+
+            // starting inside the cycle
+            let inside = Cycle {
+                start: 0,
+                length: 4,
+            };
+            assert_eq!(find_cycle(5, step).0, inside);
+            assert_eq!(find_cycle_brent(5, step), inside);
+            let fixed_point = Cycle {
+                start: 0,
+                length: 1,
+            };
+            assert_eq!(find_cycle_brent(7, |&n| n), fixed_point);
+        }
+
+        #[test]
+        fn reduce() {
+            let cycle = Cycle {
+                start: 3,
+                length: 4,
+            };
+            let reduced: Vec<_> = (0..12).map(|n| cycle.reduce(n)).collect();
+            assert_eq!(reduced, vec![0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, 3]);
+        }
+
+        #[test]
+        fn nth_matches_stepping() {
+            let mut current = 0;
+            for count in 0..30 {
+                assert_eq!(nth_state(0, count, step), current, "count {count}");
+                current = step(&current);
+            }
+            assert_eq!(nth_state(0, 1_000_000_000_000, step), 4);
+        }
+    }
+}
+
+pub mod sequence {
+    //! Finite differences of integer sequences, for extrapolating polynomial growth
+    //!
+    //! A sequence whose differences become constant after `k` rounds is a polynomial of
+    //! degree `k` in the index, so it can be continued in either direction.
+
+    use crate::math::Rational;
+
+    /// Rows of repeated differences, from the sequence down to the first constant row
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct DifferenceTable {
+        rows: Vec<Vec<i64>>,
+    }
+    impl DifferenceTable {
+        /// Returns `None` for an empty sequence, or if a difference overflows
+        pub fn new(values: &[i64]) -> Option<Self> {
+            if values.is_empty() {
+                return None;
+            }
+            let mut rows = vec![values.to_vec()];
+            loop {
+                let last = rows.last().expect("nonempty rows");
+                if last.iter().all(|&value| value == last[0]) {
+                    break;
+                }
+                let diffs = last
+                    .windows(2)
+                    .map(|pair| pair[1].checked_sub(pair[0]))
+                    .collect::<Option<_>>()?;
+                rows.push(diffs);
+            }
+            Some(Self { rows })
+        }
+        pub fn rows(&self) -> &[Vec<i64>] {
+            &self.rows
+        }
+        /// Number of values in the original sequence
+        pub fn len(&self) -> usize {
+            self.rows[0].len()
+        }
+        /// Always false, since there is no table for an empty sequence
+        pub fn is_empty(&self) -> bool {
+            false
+        }
+        /// Degree of the lowest-degree polynomial through the values (zero for constants)
+        pub fn degree(&self) -> usize {
+            self.rows.len() - 1
+        }
+        /// Returns true if the values confirm the polynomial, with at least two entries in the
+        /// constant row (otherwise any sequence fits, with degree one less than its length)
+        pub fn is_confirmed(&self) -> bool {
+            self.rows.last().expect("nonempty rows").len() >= 2
+        }
+        /// Returns the value at the index, which may be before (negative) or after the sequence
+        ///
+        /// Returns `None` on overflow.
+        pub fn value_at(&self, index: i64) -> Option<i64> {
+            // Newton forward form: sum of the k-th difference times `binomial(index, k)`
+            let index = i128::from(index);
+            let mut binomial: i128 = 1;
+            let mut sum: i128 = 0;
+            for (k, row) in self.rows.iter().enumerate() {
+                if k > 0 {
+                    let k = k as i128;
+                    binomial = binomial.checked_mul(index - k + 1)? / k;
+                }
+                sum = sum.checked_add(i128::from(row[0]).checked_mul(binomial)?)?;
+            }
+            i64::try_from(sum).ok()
+        }
+        /// Returns the value after the last value of the sequence
+        pub fn next_value(&self) -> Option<i64> {
+            self.value_at(self.len() as i64)
+        }
+        /// Returns the value before the first value of the sequence
+        pub fn previous_value(&self) -> Option<i64> {
+            self.value_at(-1)
+        }
+        /// Returns the polynomial in the index, with exact coefficients
+        ///
+        /// Returns `None` on overflow, which high degrees reach as the factorials grow.
+        pub fn polynomial(&self) -> Option<Polynomial> {
+            // expand each `binomial(x, k) = x (x-1) ... (x-k+1) / k!` into powers of x
+            let mut falling = vec![1_i128];
+            let mut factorial: i128 = 1;
+            let mut coefficients = vec![Rational::ZERO; self.rows.len()];
+            for (k, row) in self.rows.iter().enumerate() {
+                if k > 0 {
+                    let shift = k as i128 - 1;
+                    // multiply by (x - shift)
+                    let mut next = vec![0_i128; falling.len() + 1];
+                    for (power, &coefficient) in falling.iter().enumerate() {
+                        next[power + 1] = next[power + 1].checked_add(coefficient)?;
+                        next[power] = next[power].checked_sub(shift.checked_mul(coefficient)?)?;
+                    }
+                    falling = next;
+                    factorial = factorial.checked_mul(k as i128)?;
+                }
+                let scale = Rational::new(i128::from(row[0]), factorial);
+                for (power, &coefficient) in falling.iter().enumerate() {
+                    let term = scale.checked_mul(Rational::from(coefficient))?;
+                    coefficients[power] = coefficients[power].checked_add(term)?;
+                }
+            }
+            Some(Polynomial::new(coefficients))
+        }
+    }
+
+    /// Polynomial with exact rational coefficients
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Polynomial {
+        /// Coefficient for each power, lowest first, without trailing zeros
+        coefficients: Vec<Rational>,
+    }
+    impl Polynomial {
+        pub fn new(mut coefficients: Vec<Rational>) -> Self {
+            while coefficients.last() == Some(&Rational::ZERO) {
+                coefficients.pop();
+            }
+            Self { coefficients }
+        }
+        /// Coefficient for each power, lowest first
+        pub fn coefficients(&self) -> &[Rational] {
+            &self.coefficients
+        }
+        /// Degree of the highest nonzero term (zero for constants, including zero)
+        pub fn degree(&self) -> usize {
+            self.coefficients.len().saturating_sub(1)
+        }
+        /// Value at `x`, or `None` on overflow
+        pub fn evaluate(&self, x: i64) -> Option<Rational> {
+            let x = Rational::from(i128::from(x));
+            self.coefficients
+                .iter()
+                .rev()
+                .try_fold(Rational::ZERO, |sum, &coefficient| {
+                    sum.checked_mul(x)?.checked_add(coefficient)
+                })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::DifferenceTable;
+        use crate::math::Rational;
+
+        #[test]
+        fn extrapolates() {
+            let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+            assert_eq!(table.degree(), 3);
+            assert!(table.is_confirmed());
+            assert_eq!(table.rows()[3], vec![2, 2, 2]);
+            assert_eq!(table.next_value(), Some(68));
+            assert_eq!(table.previous_value(), Some(5));
+            assert_eq!(table.value_at(2), Some(16));
+
+            let constant = DifferenceTable::new(&[7, 7]).unwrap();
+            assert_eq!(constant.degree(), 0);
+            assert_eq!(constant.value_at(-1000), Some(7));
+
+            let single = DifferenceTable::new(&[4]).unwrap();
+            assert!(!single.is_confirmed());
+            assert_eq!(single.next_value(), Some(4));
+            assert_eq!(DifferenceTable::new(&[]), None);
+
+            let huge = DifferenceTable::new(&[0, i64::MAX / 2, i64::MAX - 1]).unwrap();
+            assert_eq!(huge.value_at(3), None);
+            assert_eq!(DifferenceTable::new(&[i64::MIN, i64::MAX]), None);
+            assert_eq!(DifferenceTable::new(&[0, i64::MAX, 0]), None);
+        }
+
+        #[test]
+        fn steps_match_values() {
+            let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+            // stepping forward one difference row at a time
+            let mut rows = table.rows().to_vec();
+            for index in 6..20 {
+                for row in (0..rows.len() - 1).rev() {
+                    let next = rows[row].last().unwrap() + rows[row + 1].last().unwrap();
+                    rows[row].push(next);
+                }
+                assert_eq!(table.value_at(index), rows[0].last().copied());
+            }
+        }
+
+        #[test]
+        fn polynomial() {
+            // triangular numbers (x + 1)(x + 2) / 2
+            let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+            let polynomial = table.polynomial().unwrap();
+            assert_eq!(polynomial.degree(), 2);
+            assert_eq!(
+                polynomial.coefficients(),
+                [Rational::from(1), Rational::new(3, 2), Rational::new(1, 2)]
+            );
+            for index in -10..10 {
+                let value = table.value_at(index).map(i128::from);
+                assert_eq!(polynomial.evaluate(index).unwrap().to_integer(), value);
+            }
+
+            let cubic = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+            let cubic = cubic.polynomial().unwrap();
+            assert_eq!(cubic.degree(), 3);
+            assert_eq!(cubic.evaluate(6), Some(Rational::from(68)));
+            assert_eq!(cubic.evaluate(i64::MAX), None);
+
+            let zero = DifferenceTable::new(&[0, 0, 0]).unwrap();
+            assert_eq!(zero.polynomial().unwrap().coefficients(), []);
+
+            // the factorial of the degree overflows i128 past 33
+            let alternating: Vec<i64> = (0..40).map(|i| if i % 2 == 0 { 1 } else { -1 }).collect();
+            let table = DifferenceTable::new(&alternating).unwrap();
+            assert_eq!(table.degree(), 39);
+            assert_eq!(table.polynomial(), None);
+        }
+    }
 }
@@ -0,0 +1,219 @@
+//! Expected answers, for checking the results of a run
+//!
+//! The manifest is either TOML (for files ending in `.toml`), with a table per day:
+//!
+//! ```toml
+//! [day14]
+//! part1 = 136
+//! part2 = 64
+//! ```
+//!
+//! or plain text, with one `day part answer` per line (lines starting with `#` are ignored):
+//!
+//! ```text
+//! 14 1 136
+//! 14 2 64
+//! ```
+
+use crate::Part;
+use anyhow::Context;
+use serde_json::{Number, Value};
+use std::{collections::BTreeMap, path::Path};
+
+/// Expected answer for each day and part
+///
+/// Answers are compared by value (e.g. the number `136`, not the text `"136"`),
+/// matching the typed answers from each [`crate::Solver`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Manifest(BTreeMap<(u8, Part), Value>);
+
+impl Manifest {
+    /// Reads the manifest from the file, choosing the format by the file extension
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading manifest {path:?}"))?;
+        let manifest = if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml(&text)
+        } else {
+            Self::from_lines(&text)
+        };
+        manifest.with_context(|| format!("in manifest {path:?}"))
+    }
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let tables: BTreeMap<String, BTreeMap<String, toml::Value>> = toml::from_str(text)?;
+
+        let mut expected = BTreeMap::new();
+        for (day_key, parts) in tables {
+            let Some(day) = day_key.strip_prefix("day").and_then(|day| day.parse().ok()) else {
+                anyhow::bail!("invalid table [{day_key}], expected a day such as [day14]")
+            };
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => anyhow::bail!("invalid key {part_key:?} for day {day}"),
+                };
+                let answer = serde_json::to_value(answer)?;
+                expected.insert((day, part), answer);
+            }
+        }
+        Ok(Self(expected))
+    }
+    pub fn from_lines(text: &str) -> anyhow::Result<Self> {
+        let mut expected = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(day), Some(part), Some(answer), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                anyhow::bail!("expected `day part answer` on line {line_number}: {line:?}")
+            };
+            let day = day
+                .parse()
+                .with_context(|| format!("invalid day {day:?} on line {line_number}"))?;
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => anyhow::bail!("invalid part {part:?} on line {line_number}"),
+            };
+            let is_integer = answer
+                .strip_prefix('-')
+                .unwrap_or(answer)
+                .bytes()
+                .all(|b| b.is_ascii_digit());
+            let answer = match answer.parse::<Number>() {
+                // integers beyond 64 bits are text, the same as the answers
+                Ok(number) if number.is_f64() && is_integer => Value::String(answer.to_owned()),
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::String(answer.to_owned()),
+            };
+            if expected.insert((day, part), answer).is_some() {
+                anyhow::bail!("duplicate answer for day {day} part {part} on line {line_number}");
+            }
+        }
+        Ok(Self(expected))
+    }
+    /// Compares the answer against the expected value (if any)
+    pub fn check(&self, day: u8, part: Part, actual: &Value) -> Check {
+        match self.0.get(&(day, part)) {
+            None => Check::Missing,
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// Result of comparing one answer against the [`Manifest`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: Value,
+    },
+    /// No expected answer is listed
+    Missing,
+}
+impl Check {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+    /// Short label for machine-readable output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail { .. } => "fail",
+            Self::Missing => "missing",
+        }
+    }
+}
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Self::Missing => write!(f, "not in manifest"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Check, Manifest};
+    use crate::Part;
+    use serde_json::json;
+
+    #[test]
+    fn toml() {
+        let manifest = Manifest::from_toml(
+            "[day14]
+part1 = 136
+part2 = 64
+
+[day15]
+part1 = \"abc\"",
+        )
+        .unwrap();
+        assert_eq!(manifest.check(14, Part::One, &json!(136)), Check::Pass);
+        assert_eq!(
+            manifest.check(14, Part::Two, &json!(65)),
+            Check::Fail {
+                expected: json!(64)
+            }
+        );
+        assert_eq!(manifest.check(15, Part::One, &json!("abc")), Check::Pass);
+        assert_eq!(manifest.check(15, Part::Two, &json!(1)), Check::Missing);
+    }
+    #[test]
+    fn toml_invalid() {
+        assert!(Manifest::from_toml("[14]\npart1 = 1").is_err());
+        assert!(Manifest::from_toml("[day14]\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn lines() {
+        let manifest = Manifest::from_lines(
+            "# day part answer
+14 1 136
+
+14 2 64
+15 1 abc",
+        )
+        .unwrap();
+        let from_toml = Manifest::from_toml(
+            "[day14]
+part1 = 136
+part2 = 64
+[day15]
+part1 = \"abc\"",
+        )
+        .unwrap();
+        assert_eq!(manifest, from_toml);
+    }
+    #[test]
+    fn lines_compare_typed() {
+        let manifest = Manifest::from_lines("1 1 142").unwrap();
+        assert_eq!(manifest.check(1, Part::One, &json!(142)), Check::Pass);
+        assert!(manifest.check(1, Part::One, &json!("142")).is_fail());
+    }
+    #[test]
+    fn lines_wide_integers() {
+        let wide = u128::MAX.to_string();
+        let manifest = Manifest::from_lines(&format!("6 2 {wide}\n6 1 1.5")).unwrap();
+        assert_eq!(manifest.check(6, Part::Two, &json!(wide)), Check::Pass);
+        assert_eq!(manifest.check(6, Part::One, &json!(1.5)), Check::Pass);
+    }
+    #[test]
+    fn lines_invalid() {
+        assert!(Manifest::from_lines("14 1").is_err());
+        assert!(Manifest::from_lines("14 3 1").is_err());
+        assert!(Manifest::from_lines("14 1 1 extra").is_err());
+        assert!(Manifest::from_lines("14 1 1\n14 1 2").is_err());
+    }
+}
@@ -0,0 +1,444 @@
+//! Sets of values stored as sorted ranges, and maps that shift ranges of values
+
+use std::ops::Range;
+
+/// Set of values, stored as sorted ranges that are nonempty, disjoint and non-adjacent
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+impl<T: Copy + Ord> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// Smallest value in the set
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= *value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(value))
+    }
+    /// Adds the range, merging with overlapping or adjacent ranges
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // ranges touching the new range are merged into it
+        let first = self
+            .ranges
+            .partition_point(|existing| existing.end < range.start);
+        let last = self
+            .ranges
+            .partition_point(|existing| existing.start <= range.end);
+        let merged = match self.ranges.get(first..last) {
+            Some([head, .., tail]) | Some([head @ tail]) => {
+                head.start.min(range.start)..tail.end.max(range.end)
+            }
+            _ => range,
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a_iter, mut b_iter) = (self.ranges.iter(), other.ranges.iter());
+        let (mut a, mut b) = (a_iter.next(), b_iter.next());
+        while let (Some(a_range), Some(b_range)) = (a, b) {
+            let start = a_range.start.max(b_range.start);
+            let end = a_range.end.min(b_range.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // advance whichever range ends first
+            if a_range.end <= b_range.end {
+                a = a_iter.next();
+            } else {
+                b = b_iter.next();
+            }
+        }
+        // NOTE: pieces of disjoint, non-adjacent ranges are also disjoint and non-adjacent
+        Self { ranges }
+    }
+    /// Values in this set and not the other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip other ranges entirely before this range
+            while others.next_if(|other| other.end <= start).is_some() {}
+            while let Some(other) = others.peek() {
+                if other.start >= range.end {
+                    break;
+                }
+                if start < other.start {
+                    ranges.push(start..other.start);
+                }
+                start = start.max(other.end);
+                if other.end > range.end {
+                    // extends into the next range
+                    break;
+                }
+                others.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+impl RangeSet<u64> {
+    /// Values in `0..u64::MAX` not in the set
+    pub fn complement(&self) -> Self {
+        Self::from(0..u64::MAX).difference(self)
+    }
+    /// Number of values in the set
+    pub fn count(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+    /// Adds the offset to every value, dropping values shifted outside `0..u64::MAX`
+    pub fn shift(&self, offset: i64) -> Self {
+        self.iter()
+            .filter_map(|range| shift_range(range, offset))
+            .collect()
+    }
+}
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+impl<T: std::fmt::Debug> std::fmt::Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+/// Returns the range shifted by the offset, clipped to `0..u64::MAX`, or `None` if empty
+fn shift_range(range: Range<u64>, offset: i64) -> Option<Range<u64>> {
+    let shift = |bound: u64| {
+        let shifted = i128::from(bound) + i128::from(offset);
+        u64::try_from(shifted.clamp(0, i128::from(u64::MAX))).expect("clamped to u64")
+    };
+    let shifted = shift(range.start)..shift(range.end);
+    (!shifted.is_empty()).then_some(shifted)
+}
+
+/// Map that adds an offset to values in each range, and leaves other values unchanged
+///
+/// The pieces are sorted and disjoint, without zero offsets or adjacent equal offsets.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<(Range<u64>, i64)>,
+}
+impl PiecewiseMap {
+    /// Returns `None` if any of the ranges overlap
+    pub fn new(pieces: impl IntoIterator<Item = (Range<u64>, i64)>) -> Option<Self> {
+        let mut pieces: Vec<_> = pieces
+            .into_iter()
+            .filter(|(range, _)| !range.is_empty())
+            .collect();
+        pieces.sort_by_key(|(range, _)| range.start);
+        if pieces
+            .windows(2)
+            .any(|pair| pair[0].0.end > pair[1].0.start)
+        {
+            return None;
+        }
+        Some(Self::normalized(pieces))
+    }
+    /// Removes identity pieces, and merges adjacent pieces with equal offsets
+    fn normalized(pieces: Vec<(Range<u64>, i64)>) -> Self {
+        let mut normalized: Vec<(Range<u64>, i64)> = vec![];
+        for (range, offset) in pieces {
+            if offset == 0 {
+                continue;
+            }
+            match normalized.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => normalized.push((range, offset)),
+            }
+        }
+        Self { pieces: normalized }
+    }
+    /// Ranges with nonzero offsets, sorted by start
+    pub fn pieces(&self) -> &[(Range<u64>, i64)] {
+        &self.pieces
+    }
+    /// Offset applied to the value (zero outside all pieces)
+    pub fn offset_at(&self, value: u64) -> i64 {
+        let index = self.pieces.partition_point(|(range, _)| range.end <= value);
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.contains(&value) => *offset,
+            _ => 0,
+        }
+    }
+    /// Returns the mapped value, or `None` if it is outside the `u64` bounds
+    pub fn apply(&self, value: u64) -> Option<u64> {
+        value.checked_add_signed(self.offset_at(value))
+    }
+    /// Returns the mapped values, dropping those outside `0..u64::MAX`
+    pub fn apply_set(&self, set: &RangeSet<u64>) -> RangeSet<u64> {
+        self.segments()
+            .flat_map(|(range, offset)| {
+                set.intersection(&RangeSet::from(range))
+                    .iter()
+                    .filter_map(move |piece| shift_range(piece, offset))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+    /// Returns the map that applies this map, then the next map
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+        for (range, offset) in self.segments() {
+            let Some(image) = shift_range(range.clone(), offset) else {
+                // every value leaves the bounds, so keep the offset that overflows
+                pieces.push((range, offset));
+                continue;
+            };
+            // values clipped from the image also leave the bounds
+            let image_start = i128::from(range.start) + i128::from(offset);
+            let image_end = i128::from(range.end) + i128::from(offset);
+            let preimage = |bound: u64| {
+                u64::try_from(i128::from(bound) - i128::from(offset)).expect("within the range")
+            };
+            if image_start < i128::from(image.start) {
+                pieces.push((range.start..preimage(image.start), offset));
+            }
+            for (next_range, next_offset) in next.segments() {
+                let start = image.start.max(next_range.start);
+                let end = image.end.min(next_range.end);
+                if start < end {
+                    pieces.push((preimage(start)..preimage(end), offset + next_offset));
+                }
+            }
+            if image_end > i128::from(image.end) {
+                pieces.push((preimage(image.end)..range.end, offset));
+            }
+        }
+        Self::normalized(pieces)
+    }
+    /// Pieces and identity gaps, covering `0..u64::MAX` in order
+    fn segments(&self) -> impl Iterator<Item = (Range<u64>, i64)> + '_ {
+        let mut start = 0;
+        self.pieces
+            .iter()
+            .cloned()
+            .chain([(u64::MAX..u64::MAX, 0)])
+            .flat_map(move |(range, offset)| {
+                let gap = (start..range.start, 0);
+                start = range.end;
+                [gap, (range, offset)]
+            })
+            .filter(|(range, _)| !range.is_empty())
+    }
+}
+impl std::fmt::Debug for PiecewiseMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.pieces.iter().map(|(range, offset)| (range, offset)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // this is needed for many tests!
+    #![allow(clippy::single_range_in_vec_init)]
+
+    use super::{PiecewiseMap, RangeSet};
+    use proptest::prelude::*;
+    use std::{collections::BTreeSet, ops::Range};
+
+    fn set(ranges: &[Range<u64>]) -> RangeSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        let mut ranges = set(&[10..20, 30..40]);
+        assert_eq!(ranges.ranges(), [10..20, 30..40]);
+        ranges.insert(20..25);
+        assert_eq!(ranges.ranges(), [10..25, 30..40]);
+        ranges.insert(0..5);
+        ranges.insert(3..3);
+        assert_eq!(ranges.ranges(), [0..5, 10..25, 30..40]);
+        ranges.insert(4..35);
+        assert_eq!(ranges.ranges(), [0..40]);
+        assert!(ranges.contains(&39));
+        assert!(!ranges.contains(&40));
+        assert_eq!(ranges.count(), 40);
+    }
+
+    #[test]
+    fn set_operations() {
+        // cases from the original two-range intersection: identical, disjoint, contained and
+        // partially overlapping
+        let cases = [
+            (2..5, 2..5, vec![2..5], vec![], vec![2..5]),
+            (2..5, 7..9, vec![], vec![2..5], vec![2..5, 7..9]),
+            (
+                19..93,
+                25..30,
+                vec![25..30],
+                vec![19..25, 30..93],
+                vec![19..93],
+            ),
+            (19..30, 25..30, vec![25..30], vec![19..25], vec![19..30]),
+            (25..93, 25..30, vec![25..30], vec![30..93], vec![25..93]),
+            (5..30, 10..90, vec![10..30], vec![5..10], vec![5..90]),
+            (5..30, 10..30, vec![10..30], vec![5..10], vec![5..30]),
+            (10..30, 10..90, vec![10..30], vec![], vec![10..90]),
+        ];
+        for (a, b, both, a_only, union) in cases {
+            let (a, b) = (set(&[a]), set(&[b]));
+            assert_eq!(a.intersection(&b), set(&both), "{a:?} & {b:?}");
+            assert_eq!(b.intersection(&a), set(&both), "{b:?} & {a:?}");
+            assert_eq!(a.difference(&b), set(&a_only), "{a:?} - {b:?}");
+            assert_eq!(a.union(&b), set(&union), "{a:?} | {b:?}");
+        }
+
+        let complement = set(&[0..5, 10..20]).complement();
+        assert_eq!(complement.ranges(), [5..10, 20..u64::MAX]);
+        assert_eq!(set(&[]).complement().complement(), set(&[]));
+    }
+
+    #[test]
+    fn shift() {
+        let ranges = set(&[5..10, 20..30]);
+        assert_eq!(ranges.shift(-7), set(&[0..3, 13..23]));
+        assert_eq!(ranges.shift(-25), set(&[0..5]));
+        assert_eq!(set(&[u64::MAX - 5..u64::MAX]).shift(3).count(), 2);
+    }
+
+    #[test]
+    fn map_apply() {
+        let map = PiecewiseMap::new([(20..35, 10), (5..10, -5), (10..12, -5)]).unwrap();
+        assert_eq!(map.pieces(), [(5..12, -5), (20..35, 10)]);
+        assert_eq!(map.apply(4), Some(4));
+        assert_eq!(map.apply(5), Some(0));
+        assert_eq!(map.apply(34), Some(44));
+        assert_eq!(map.apply(35), Some(35));
+        assert_eq!(map.apply_set(&set(&[0..30])), set(&[0..7, 12..20, 30..40]));
+        assert_eq!(PiecewiseMap::new([(0..10, 1), (9..20, 2)]), None);
+    }
+
+    #[test]
+    fn map_then() {
+        let first = PiecewiseMap::new([(10..30, 20)]).unwrap();
+        let second = PiecewiseMap::new([(5..40, 2), (45..70, 100)]).unwrap();
+        assert_eq!(
+            first.then(&second).pieces(),
+            [
+                (5..10, 2),
+                (10..20, 22),
+                (20..25, 20),
+                (25..30, 120),
+                (30..40, 2),
+                (45..70, 100),
+            ]
+        );
+
+        // offsets that cancel become the identity
+        let inverse = PiecewiseMap::new([(30..50, -20)]).unwrap();
+        assert_eq!(first.then(&inverse).pieces(), [(30..50, -20)]);
+    }
+
+    fn arb_set() -> impl Strategy<Value = RangeSet<u64>> {
+        prop::collection::vec((0..60_u64, 0..10_u64), 0..5).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| start..start + len)
+                .collect()
+        })
+    }
+    fn arb_map() -> impl Strategy<Value = PiecewiseMap> {
+        prop::collection::vec((0..60_u64, 0..10_u64, -30..30_i64), 0..5).prop_map(|pieces| {
+            // drop overlapping pieces
+            let mut used = RangeSet::new();
+            let pieces: Vec<_> = pieces
+                .into_iter()
+                .map(|(start, len, offset)| (start..start + len, offset))
+                .filter(|(range, _)| {
+                    let overlaps = !used.intersection(&RangeSet::from(range.clone())).is_empty();
+                    used.insert(range.clone());
+                    !overlaps
+                })
+                .collect();
+            PiecewiseMap::new(pieces).expect("no overlaps")
+        })
+    }
+    fn values(set: &RangeSet<u64>) -> BTreeSet<u64> {
+        set.iter().flatten().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn set_ops_match_btree(a in arb_set(), b in arb_set()) {
+            let (a_values, b_values) = (values(&a), values(&b));
+            prop_assert_eq!(values(&a.union(&b)), &a_values | &b_values);
+            prop_assert_eq!(values(&a.intersection(&b)), &a_values & &b_values);
+            prop_assert_eq!(values(&a.difference(&b)), &a_values - &b_values);
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                // normalized: nonempty, sorted, disjoint and non-adjacent
+                prop_assert!(set.ranges().iter().all(|range| !range.is_empty()));
+                prop_assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start));
+            }
+            for value in 0..80 {
+                prop_assert_eq!(a.contains(&value), a_values.contains(&value));
+                prop_assert_eq!(a.complement().contains(&value), !a_values.contains(&value));
+            }
+        }
+        #[test]
+        fn map_matches_values(map in arb_map(), next in arb_map(), set in arb_set()) {
+            let composed = map.then(&next);
+            for value in 0..100 {
+                let expected = map.apply(value).and_then(|value| next.apply(value));
+                prop_assert_eq!(composed.apply(value), expected, "{}", value);
+            }
+            let mapped: BTreeSet<_> = values(&set).into_iter().filter_map(|value| map.apply(value)).collect();
+            prop_assert_eq!(values(&map.apply_set(&set)), mapped);
+        }
+    }
+}
@@ -0,0 +1,519 @@
+//! Dispatches puzzle inputs to the [`Solver`] for each day
+
+use crate::{
+    bench::{self, Spread, Summary},
+    days,
+    manifest::{Check, Manifest},
+    print::ConsolePrinter,
+    Args, BenchArgs, Command, Format, GenArgs, InputArgs, ParseError, Part, PartSelection,
+    RuleArgs, RunArgs, Solver,
+};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Type-erased [`Solver`], for selecting the day at runtime
+pub struct Entry {
+    pub day: u8,
+    pub name: &'static str,
+    run_fn: fn(&str, PartSelection, &RuleArgs) -> anyhow::Result<Answers>,
+    animate_fn: Option<fn(&str, &RuleArgs) -> anyhow::Result<()>>,
+}
+impl Entry {
+    pub const fn new<S: Solver>(name: &'static str) -> Self {
+        Self {
+            day: S::DAY,
+            name,
+            run_fn: run_solver::<S>,
+            animate_fn: None,
+        }
+    }
+    /// Adds an interactive terminal view of the solver, for `--animate`
+    pub const fn with_animation(
+        self,
+        animate_fn: fn(&str, &RuleArgs) -> anyhow::Result<()>,
+    ) -> Self {
+        Self {
+            animate_fn: Some(animate_fn),
+            ..self
+        }
+    }
+    /// Parses the input and solves the selected parts
+    pub fn run(&self, input: &str, parts: PartSelection) -> anyhow::Result<Answers> {
+        self.run_with_rules(input, parts, &RuleArgs::default())
+    }
+    /// Same as [`Self::run`], with the puzzle rules adjusted
+    pub fn run_with_rules(
+        &self,
+        input: &str,
+        parts: PartSelection,
+        rules: &RuleArgs,
+    ) -> anyhow::Result<Answers> {
+        (self.run_fn)(input, parts, rules)
+    }
+    /// Animates the input on the terminal, if the day supports it
+    pub fn animate(&self, input: &str, rules: &RuleArgs) -> anyhow::Result<()> {
+        let Some(animate_fn) = self.animate_fn else {
+            anyhow::bail!("no animation for day {}", self.day)
+        };
+        animate_fn(input, rules)
+    }
+}
+fn run_solver<S: Solver>(
+    input: &str,
+    parts: PartSelection,
+    rules: &RuleArgs,
+) -> anyhow::Result<Answers> {
+    let start = Instant::now();
+    let parsed = S::parse_with_rules(input, rules)?;
+    let parse = start.elapsed();
+
+    let part1 = parts
+        .includes(Part::One)
+        .then(|| timed(|| S::part1(&parsed)))
+        .transpose()?;
+    let part2 = parts
+        .includes(Part::Two)
+        .then(|| timed(|| S::part2(&parsed)))
+        .transpose()?;
+
+    let (part1, part1_time) = part1.unzip();
+    let (part2, part2_time) = part2.unzip();
+    Ok(Answers {
+        part1: part1.as_ref().map(Answer::new),
+        part2: part2.as_ref().map(Answer::new),
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    })
+}
+fn timed<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<(T, Duration)> {
+    let start = Instant::now();
+    let value = f()?;
+    Ok((value, start.elapsed()))
+}
+
+/// Answers for the selected parts (`None` if not selected)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub timings: Timings,
+}
+
+/// Answer for one part, keeping the typed value for machine-readable output
+///
+/// Integers beyond 64 bits (e.g. `u128`) are not JSON numbers, so their value is the text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub text: String,
+    pub value: serde_json::Value,
+}
+impl Answer {
+    fn new<T: std::fmt::Display + serde::Serialize>(answer: &T) -> Self {
+        let text = answer.to_string();
+        let value = serde_json::to_value(answer)
+            .unwrap_or_else(|_| serde_json::Value::String(text.clone()));
+        Self { text, value }
+    }
+}
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonReport<'a> {
+    day: u8,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<&'a serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<&'a serde_json::Value>,
+    timings: JsonTimings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<JsonCheck>,
+}
+#[derive(serde::Serialize)]
+struct JsonCheck {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<&'static str>,
+}
+#[derive(serde::Serialize)]
+struct JsonTimings {
+    parse_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2_ms: Option<f64>,
+}
+impl From<Timings> for JsonTimings {
+    fn from(timings: Timings) -> Self {
+        let Timings {
+            parse,
+            part1,
+            part2,
+        } = timings;
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        Self {
+            parse_ms: ms(parse),
+            part1_ms: part1.map(ms),
+            part2_ms: part2.map(ms),
+        }
+    }
+}
+
+/// Returns the solver for the specified day, if one exists
+pub fn find(day: u8) -> Option<&'static Entry> {
+    days::ALL.iter().find(|entry| entry.day == day)
+}
+
+pub fn main(args: Args) -> anyhow::Result<()> {
+    let Args {
+        format,
+        verbose,
+        quiet,
+        command,
+    } = args;
+    crate::log::init(verbose, quiet);
+    let result = match command {
+        Command::Run(run_args) => run(run_args, format),
+        Command::Bench(bench_args) => run_bench(bench_args, format),
+        Command::Gen(gen_args) => run_gen(gen_args),
+    };
+    if let Err(err) = &result {
+        render_parse_error(err);
+    }
+    result
+}
+
+/// Shows where parsing failed, if the error came from a [`ParseError`]
+fn render_parse_error(err: &anyhow::Error) {
+    let Some(parse_error) = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ParseError>())
+    else {
+        return;
+    };
+    let mut printer = ConsolePrinter::from(console::Term::stderr());
+    if let Err(render_err) = parse_error.render(&mut printer) {
+        crate::log_debug!("failed to show parse error location: {render_err}");
+    }
+}
+
+/// Calls the function with each selected day and its input
+///
+/// The filename is provided only when there are multiple input files for the day.
+fn for_each_input(
+    args: InputArgs,
+    mut f: impl FnMut(&'static Entry, Option<&Path>, &str) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let InputArgs {
+        day,
+        filenames,
+        all,
+        part: _,
+        rules: _,
+    } = args;
+
+    if let Some(inputs_dir) = all {
+        for entry in days::ALL {
+            let filename = inputs_dir.join(format!("day{}.txt", entry.day));
+            if !filename.exists() {
+                crate::log_info!("Day {}: skipped, no input {filename:?}", entry.day);
+                continue;
+            }
+            let input = crate::get_input_string(Some(&filename))?;
+            f(entry, None, &input)?;
+        }
+        Ok(())
+    } else {
+        let Some(day) = day else {
+            anyhow::bail!("no day specified")
+        };
+        let Some(entry) = find(day) else {
+            anyhow::bail!("no solver for day {day}")
+        };
+        let stdin_count = filenames
+            .iter()
+            .filter(|filename| filename.as_path() == Path::new(crate::STDIN_FILENAME))
+            .count();
+        if stdin_count > 1 {
+            anyhow::bail!("stdin can only be read once, but `-` was specified {stdin_count} times")
+        }
+        if filenames.len() > 1 {
+            for filename in &filenames {
+                let input = crate::get_input_string(Some(filename))?;
+                f(entry, Some(filename), &input)?;
+            }
+            Ok(())
+        } else {
+            let input = crate::get_input_string(filenames.first().map(PathBuf::as_path))?;
+            f(entry, None, &input)
+        }
+    }
+}
+
+fn run(args: RunArgs, format: Format) -> anyhow::Result<()> {
+    let RunArgs {
+        input,
+        check,
+        animate,
+    } = args;
+    let rules = input.rules.clone();
+    if animate {
+        return for_each_input(input, |entry, _filename, input| {
+            entry.animate(input, &rules)
+        });
+    }
+    let manifest = check.as_deref().map(Manifest::load).transpose()?;
+    let manifest = manifest.as_ref();
+    let parts = input.part;
+
+    let mut failures = 0;
+    for_each_input(input, |entry, filename, input| {
+        failures += run_entry(entry, filename, input, (parts, &rules), format, manifest)?;
+        Ok(())
+    })?;
+
+    if failures > 0 {
+        anyhow::bail!("{failures} answer(s) did not match the manifest")
+    }
+    Ok(())
+}
+
+/// Returns the number of answers not matching the manifest
+fn run_entry(
+    entry: &Entry,
+    filename: Option<&Path>,
+    input: &str,
+    (parts, rules): (PartSelection, &RuleArgs),
+    format: Format,
+    manifest: Option<&Manifest>,
+) -> anyhow::Result<usize> {
+    let Entry { day, name, .. } = *entry;
+    let Answers {
+        part1,
+        part2,
+        timings,
+    } = entry.run_with_rules(input, parts, rules)?;
+    let check = |part, answer: &Option<Answer>| {
+        manifest
+            .zip(answer.as_ref())
+            .map(|(manifest, answer)| manifest.check(day, part, &answer.value))
+    };
+    let check1 = check(Part::One, &part1);
+    let check2 = check(Part::Two, &part2);
+    match format {
+        Format::Text => {
+            let suffix =
+                |check: &Option<Check>| check.as_ref().map_or(String::new(), |c| format!(" ({c})"));
+            match filename {
+                Some(filename) => println!("Day {day} ({name}) {filename:?}"),
+                None => println!("Day {day} ({name})"),
+            }
+            if let Some(part1) = &part1 {
+                println!("  Part 1: {part1}{}", suffix(&check1));
+            }
+            if let Some(part2) = &part2 {
+                println!("  Part 2: {part2}{}", suffix(&check2));
+            }
+        }
+        Format::Json => {
+            let report = JsonReport {
+                day,
+                name,
+                input: filename,
+                part1: part1.as_ref().map(|answer| &answer.value),
+                part2: part2.as_ref().map(|answer| &answer.value),
+                timings: timings.into(),
+                check: manifest.map(|_| JsonCheck {
+                    part1: check1.as_ref().map(Check::label),
+                    part2: check2.as_ref().map(Check::label),
+                }),
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+    }
+    let failures = [check1, check2]
+        .iter()
+        .flatten()
+        .filter(|check| check.is_fail())
+        .count();
+    Ok(failures)
+}
+
+fn run_bench(args: BenchArgs, format: Format) -> anyhow::Result<()> {
+    let BenchArgs {
+        input,
+        iterations,
+        csv,
+    } = args;
+
+    let parts = input.part;
+    let rules = input.rules.clone();
+
+    let mut summaries = vec![];
+    for_each_input(input, |entry, filename, input| {
+        let summary = Summary::measure(entry, filename, input, (parts, &rules), iterations)?;
+        match format {
+            Format::Text => println!("{summary}"),
+            Format::Json => println!("{}", serde_json::to_string(&JsonBench::from(&summary))?),
+        }
+        summaries.push(summary);
+        Ok(())
+    })?;
+
+    if let Some(csv) = csv {
+        let file = std::fs::File::create(&csv)?;
+        bench::write_csv(std::io::BufWriter::new(file), &summaries)?;
+        crate::log_info!("Wrote benchmark results to {csv:?}");
+    }
+    Ok(())
+}
+
+fn run_gen(args: GenArgs) -> anyhow::Result<()> {
+    let GenArgs { day, size, seed } = args;
+    let seed = seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        now.map_or(0, |since_epoch| since_epoch.as_nanos() as u64)
+    });
+    crate::log_info!("Day {day}: generating size {size} with --seed {seed}");
+    let input = crate::generate::generate(day, size.get(), seed)?;
+    print!("{input}");
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct JsonBench<'a> {
+    day: u8,
+    name: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<&'a Path>,
+    iterations: u32,
+    parse: JsonSpread,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<JsonSpread>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<JsonSpread>,
+}
+#[derive(serde::Serialize)]
+struct JsonSpread {
+    min_ms: f64,
+    median_ms: f64,
+    max_ms: f64,
+}
+impl<'a> From<&'a Summary> for JsonBench<'a> {
+    fn from(summary: &'a Summary) -> Self {
+        let Summary {
+            day,
+            name,
+            ref input,
+            iterations,
+            parse,
+            part1,
+            part2,
+        } = *summary;
+        Self {
+            day,
+            name,
+            input: input.as_deref(),
+            iterations: iterations.get(),
+            parse: parse.into(),
+            part1: part1.map(JsonSpread::from),
+            part2: part2.map(JsonSpread::from),
+        }
+    }
+}
+impl From<Spread> for JsonSpread {
+    fn from(spread: Spread) -> Self {
+        let Spread { min, median, max } = spread;
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        Self {
+            min_ms: ms(min),
+            median_ms: ms(median),
+            max_ms: ms(max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, Answer};
+    use crate::{PartSelection, RuleArgs};
+
+    const DAY1_SAMPLE: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    #[test]
+    fn answer_keeps_typed_value() {
+        let number = Answer::new(&42_u64);
+        assert_eq!(number.to_string(), "42");
+        assert_eq!(number.value, serde_json::json!(42));
+
+        let text = Answer::new(&"abc");
+        assert_eq!(text.to_string(), "abc");
+        assert_eq!(text.value, serde_json::json!("abc"));
+
+        let wide = Answer::new(&u128::MAX);
+        assert_eq!(wide.to_string(), u128::MAX.to_string());
+        assert_eq!(wide.value, serde_json::json!(u128::MAX.to_string()));
+        let fits = Answer::new(&71503_u128);
+        assert_eq!(fits.value, serde_json::json!(71503));
+    }
+
+    #[test]
+    fn selected_parts() {
+        let entry = find(1).expect("day 1 exists");
+
+        let both = entry.run(DAY1_SAMPLE, PartSelection::Both).unwrap();
+        assert_eq!(both.part1.unwrap().value, serde_json::json!(142));
+        assert!(both.part2.is_some());
+        assert!(both.timings.part1.is_some() && both.timings.part2.is_some());
+
+        let only1 = entry.run(DAY1_SAMPLE, PartSelection::One).unwrap();
+        assert!(only1.part1.is_some() && only1.part2.is_none());
+        assert!(only1.timings.part2.is_none());
+
+        let only2 = entry.run(DAY1_SAMPLE, PartSelection::Two).unwrap();
+        assert!(only2.part1.is_none() && only2.part2.is_some());
+        assert!(only2.timings.part1.is_none());
+    }
+
+    #[test]
+    fn rules_only_for_supporting_days() {
+        let rules = RuleArgs {
+            sequence: Some("N".to_owned()),
+            edge: None,
+        };
+        let error = find(1)
+            .expect("day 1 exists")
+            .run_with_rules(DAY1_SAMPLE, PartSelection::Both, &rules)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "day 1 does not support --sequence");
+
+        let platform = "O\n.\n";
+        let summary = find(14)
+            .expect("day 14 exists")
+            .run_with_rules(platform, PartSelection::One, &rules)
+            .unwrap();
+        assert_eq!(summary.part1.unwrap().value, serde_json::json!(2));
+    }
+}